@@ -0,0 +1,45 @@
+//! Times `Mat4::determinant` over a batch of matrices.
+//!
+//! The SIMD path is only compiled in with `+simd128`, so run this under a WASI runtime both with
+//! and without it to compare the two:
+//!
+//! ```sh
+//! cargo bench --bench determinant --target wasm32-wasip1
+//! RUSTFLAGS="-C target-feature=+simd128" cargo bench --bench determinant --target wasm32-wasip1
+//! ```
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use webglm::mat::Mat4;
+
+const MATRICES: usize = 1024;
+const ROUNDS: usize = 1000;
+
+fn main() {
+    let mats: Vec<Mat4> = (0..MATRICES)
+        .map(|i| {
+            let f = i as f32;
+            Mat4::from_cols_array_2d([
+                [f.sin(), f.cos(), 0.5, 1.0],
+                [0.25, (2.0 * f).sin(), f.cos() * 0.5, 0.0],
+                [1.0, 0.0, (3.0 * f).cos(), f.sin() * 0.25],
+                [f * 0.001, -1.0, 0.5, 1.0],
+            ])
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut checksum = 0.0;
+    for _ in 0..ROUNDS {
+        for m in &mats {
+            checksum += black_box(m).determinant();
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "determinant: {elapsed:?} for {} matrices (checksum {checksum})",
+        MATRICES * ROUNDS
+    );
+}
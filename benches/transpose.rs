@@ -0,0 +1,80 @@
+//! Times `Mat4::transpose` over a batch of matrices.
+//!
+//! With `+simd128` this also times a transpose built from two rounds of lane shuffles, which the
+//! crate doesn't use: loading the columns into `f32x4` lanes and extracting them again costs more
+//! than the shuffles save. Run this under a WASI runtime with the flag to compare the two:
+//!
+//! ```sh
+//! RUSTFLAGS="-C target-feature=+simd128" cargo bench --bench transpose --target wasm32-wasip1
+//! ```
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use webglm::mat::{Mat4, Transpose};
+
+const MATRICES: usize = 1024;
+const ROUNDS: usize = 1000;
+
+#[cfg(target_feature = "simd128")]
+fn shuffle_transpose(m: Mat4) -> Mat4 {
+    let [c0, c1, c2, c3] = m
+        .to_cols_array_2d()
+        .map(|c| std::arch::wasm32::f32x4(c[0], c[1], c[2], c[3]));
+
+    let t0 = std::arch::wasm32::i32x4_shuffle::<0, 4, 1, 5>(c0, c1);
+    let t1 = std::arch::wasm32::i32x4_shuffle::<2, 6, 3, 7>(c0, c1);
+    let t2 = std::arch::wasm32::i32x4_shuffle::<0, 4, 1, 5>(c2, c3);
+    let t3 = std::arch::wasm32::i32x4_shuffle::<2, 6, 3, 7>(c2, c3);
+
+    Mat4::from_cols_array_2d(
+        [
+            std::arch::wasm32::i32x4_shuffle::<0, 1, 4, 5>(t0, t2),
+            std::arch::wasm32::i32x4_shuffle::<2, 3, 6, 7>(t0, t2),
+            std::arch::wasm32::i32x4_shuffle::<0, 1, 4, 5>(t1, t3),
+            std::arch::wasm32::i32x4_shuffle::<2, 3, 6, 7>(t1, t3),
+        ]
+        .map(|c| {
+            [
+                std::arch::wasm32::f32x4_extract_lane::<0>(c),
+                std::arch::wasm32::f32x4_extract_lane::<1>(c),
+                std::arch::wasm32::f32x4_extract_lane::<2>(c),
+                std::arch::wasm32::f32x4_extract_lane::<3>(c),
+            ]
+        }),
+    )
+}
+
+fn time(name: &str, mats: &[Mat4], transpose: impl Fn(Mat4) -> Mat4) {
+    let start = Instant::now();
+    let mut checksum = 0.0;
+    for _ in 0..ROUNDS {
+        for m in mats {
+            checksum += transpose(*black_box(m)).to_cols_array_2d()[0][1];
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name}: {elapsed:?} for {} matrices (checksum {checksum})",
+        MATRICES * ROUNDS
+    );
+}
+
+fn main() {
+    let mats: Vec<Mat4> = (0..MATRICES)
+        .map(|i| {
+            let f = i as f32;
+            Mat4::from_cols_array_2d([
+                [f.sin(), f.cos(), 0.5, 1.0],
+                [0.25, (2.0 * f).sin(), f.cos() * 0.5, 0.0],
+                [1.0, 0.0, (3.0 * f).cos(), f.sin() * 0.25],
+                [f * 0.001, -1.0, 0.5, 1.0],
+            ])
+        })
+        .collect();
+
+    time("transpose", &mats, Mat4::transpose);
+    #[cfg(target_feature = "simd128")]
+    time("shuffle transpose", &mats, shuffle_transpose);
+}
@@ -0,0 +1,334 @@
+use crate::{Vec3, Vec4};
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// An RGB color.
+///
+/// Wraps a [`Vec3`] so that colors and positions can't be mixed by accident: only color
+/// operations (channel-wise modulation, transfer functions) are available, and spatial ones such
+/// as the cross product are not. Convert with `From`/`Into` when the raw vector is needed.
+///
+/// ```
+/// use webglm::color::Color3;
+///
+/// let tinted = Color3::new(1.0, 0.5, 0.25) * Color3::new(0.5, 0.5, 0.5);
+/// assert_eq!(tinted, Color3::new(0.5, 0.25, 0.125));
+/// ```
+///
+/// ```compile_fail
+/// use webglm::color::Color3;
+///
+/// let normal = Color3::new(1.0, 0.0, 0.0).normalize();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Color3(Vec3);
+
+impl Color3 {
+    /// Constructs a new `Color3`
+    pub fn new(r: f32, g: f32, b: f32) -> Self {
+        Self(Vec3::new(r, g, b))
+    }
+
+    /// The red channel
+    pub fn r(&self) -> f32 {
+        self.0.x
+    }
+
+    /// The green channel
+    pub fn g(&self) -> f32 {
+        self.0.y
+    }
+
+    /// The blue channel
+    pub fn b(&self) -> f32 {
+        self.0.z
+    }
+
+    /// Converts from sRGB-encoded to linear values using the exact sRGB transfer function
+    pub fn to_linear(self) -> Self {
+        Self::new(
+            srgb_to_linear(self.r()),
+            srgb_to_linear(self.g()),
+            srgb_to_linear(self.b()),
+        )
+    }
+
+    /// Converts from linear to sRGB-encoded values using the exact sRGB transfer function
+    pub fn to_srgb(self) -> Self {
+        Self::new(
+            linear_to_srgb(self.r()),
+            linear_to_srgb(self.g()),
+            linear_to_srgb(self.b()),
+        )
+    }
+
+    /// Raises each channel to the power `exponent`
+    pub fn gamma(self, exponent: f32) -> Self {
+        Self::new(
+            self.r().powf(exponent),
+            self.g().powf(exponent),
+            self.b().powf(exponent),
+        )
+    }
+
+    /// Adds an alpha channel
+    pub fn with_alpha(self, a: f32) -> Color4 {
+        Color4::new(self.r(), self.g(), self.b(), a)
+    }
+}
+
+impl From<Vec3> for Color3 {
+    fn from(value: Vec3) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Color3> for Vec3 {
+    fn from(value: Color3) -> Self {
+        value.0
+    }
+}
+
+impl std::ops::Add for Color3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Mul<f32> for Color3 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl std::ops::Mul for Color3 {
+    type Output = Self;
+
+    /// Modulates (multiplies channel-wise) two colors
+    fn mul(self, rhs: Self) -> Self::Output {
+        let s = std::arch::wasm32::f32x4(self.0.x, self.0.y, self.0.z, 0.0);
+        let rhs = std::arch::wasm32::f32x4(rhs.0.x, rhs.0.y, rhs.0.z, 0.0);
+        let res = std::arch::wasm32::f32x4_mul(s, rhs);
+
+        Self::new(
+            std::arch::wasm32::f32x4_extract_lane::<0>(res),
+            std::arch::wasm32::f32x4_extract_lane::<1>(res),
+            std::arch::wasm32::f32x4_extract_lane::<2>(res),
+        )
+    }
+}
+
+/// An RGBA color.
+///
+/// The four-channel counterpart of [`Color3`], wrapping a [`Vec4`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Color4(Vec4);
+
+impl Color4 {
+    /// Constructs a new `Color4`
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self(Vec4::new(r, g, b, a))
+    }
+
+    /// The red channel
+    pub fn r(&self) -> f32 {
+        self.0.x
+    }
+
+    /// The green channel
+    pub fn g(&self) -> f32 {
+        self.0.y
+    }
+
+    /// The blue channel
+    pub fn b(&self) -> f32 {
+        self.0.z
+    }
+
+    /// The alpha channel
+    pub fn a(&self) -> f32 {
+        self.0.w
+    }
+
+    /// Drops the alpha channel
+    pub fn rgb(&self) -> Color3 {
+        Color3::new(self.r(), self.g(), self.b())
+    }
+
+    /// Converts the color channels from sRGB-encoded to linear values. Alpha is left as is.
+    pub fn to_linear(self) -> Self {
+        self.rgb().to_linear().with_alpha(self.a())
+    }
+
+    /// Converts the color channels from linear to sRGB-encoded values. Alpha is left as is.
+    pub fn to_srgb(self) -> Self {
+        self.rgb().to_srgb().with_alpha(self.a())
+    }
+
+    /// Raises each color channel to the power `exponent`. Alpha is left as is.
+    pub fn gamma(self, exponent: f32) -> Self {
+        self.rgb().gamma(exponent).with_alpha(self.a())
+    }
+
+    /// Multiplies the color channels by alpha, as expected by premultiplied-alpha blending
+    pub fn premultiply(self) -> Self {
+        (self.rgb() * self.a()).with_alpha(self.a())
+    }
+}
+
+impl From<Vec4> for Color4 {
+    fn from(value: Vec4) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Color4> for Vec4 {
+    fn from(value: Color4) -> Self {
+        value.0
+    }
+}
+
+impl std::ops::Add for Color4 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Mul<f32> for Color4 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl std::ops::Mul for Color4 {
+    type Output = Self;
+
+    /// Modulates (multiplies channel-wise) two colors
+    fn mul(self, rhs: Self) -> Self::Output {
+        let s = std::arch::wasm32::f32x4(self.0.x, self.0.y, self.0.z, self.0.w);
+        let rhs = std::arch::wasm32::f32x4(rhs.0.x, rhs.0.y, rhs.0.z, rhs.0.w);
+        let res = std::arch::wasm32::f32x4_mul(s, rhs);
+
+        Self::new(
+            std::arch::wasm32::f32x4_extract_lane::<0>(res),
+            std::arch::wasm32::f32x4_extract_lane::<1>(res),
+            std::arch::wasm32::f32x4_extract_lane::<2>(res),
+            std::arch::wasm32::f32x4_extract_lane::<3>(res),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srgb_round_trip() {
+        for c in [0.0, 0.04045, 1.0] {
+            let srgb = Color3::new(c, c, c);
+            let linear = srgb.to_linear();
+            let back = linear.to_srgb();
+            for (b, e) in [(back.r(), c), (back.g(), c), (back.b(), c)] {
+                assert!((b - e).abs() < 1e-6, "{c}: {back:?}");
+            }
+        }
+
+        assert_eq!(
+            Color3::new(0.0, 0.0, 0.0).to_linear(),
+            Color3::new(0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            Color3::new(1.0, 1.0, 1.0).to_linear(),
+            Color3::new(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            Color3::new(0.04045, 0.0, 0.0).to_linear().r(),
+            0.04045 / 12.92
+        );
+        assert_eq!(
+            Color4::new(0.5, 0.5, 0.5, 0.25).to_linear().a(),
+            0.25,
+            "alpha is not a color channel"
+        );
+    }
+
+    #[test]
+    fn gamma_encode_decode() {
+        let c = Color3::new(0.25, 0.5, 1.0);
+        let encoded = c.gamma(1.0 / 2.2);
+        assert!((encoded.g() - 0.5f32.powf(1.0 / 2.2)).abs() < 1e-6);
+        assert_eq!(encoded.b(), 1.0);
+
+        let decoded = encoded.gamma(2.2);
+        for (d, e) in [(decoded.r(), 0.25), (decoded.g(), 0.5), (decoded.b(), 1.0)] {
+            assert!((d - e).abs() < 1e-6, "{decoded:?}");
+        }
+
+        let c4 = c.with_alpha(0.25).gamma(2.0);
+        assert_eq!(c4, Color4::new(0.0625, 0.25, 1.0, 0.25));
+    }
+
+    #[test]
+    fn premultiply_scales_color_by_alpha() {
+        assert_eq!(
+            Color4::new(1.0, 0.5, 0.25, 0.5).premultiply(),
+            Color4::new(0.5, 0.25, 0.125, 0.5)
+        );
+        assert_eq!(
+            Color4::new(1.0, 0.5, 0.25, 0.0).premultiply(),
+            Color4::new(0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn with_alpha_and_rgb_round_trip() {
+        let c = Color3::new(0.1, 0.2, 0.3);
+        let c4 = c.with_alpha(0.4);
+        assert_eq!(c4, Color4::new(0.1, 0.2, 0.3, 0.4));
+        assert_eq!(c4.a(), 0.4);
+        assert_eq!(c4.rgb(), c);
+    }
+
+    #[test]
+    fn mul_modulates_each_channel() {
+        let (a, b) = (
+            Color3::new(0.9, -0.5, 3.0),
+            Color3::new(0.3, 0.7, 1.0 / 3.0),
+        );
+        assert_eq!(
+            a * b,
+            Color3::new(a.r() * b.r(), a.g() * b.g(), a.b() * b.b())
+        );
+
+        let (a, b) = (
+            Color4::new(0.9, -0.5, 3.0, 0.5),
+            Color4::new(0.3, 0.7, 1.0 / 3.0, 0.25),
+        );
+        assert_eq!(
+            a * b,
+            Color4::new(a.r() * b.r(), a.g() * b.g(), a.b() * b.b(), a.a() * b.a())
+        );
+    }
+}
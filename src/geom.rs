@@ -0,0 +1,449 @@
+use crate::mat::{Mat4, Transpose};
+use crate::vec::{Dot, Magnitude};
+use crate::{Vec3, Vec4};
+
+/// An axis-aligned bounding box
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    /// The corner with the smallest coordinates
+    pub min: Vec3,
+    /// The corner with the largest coordinates
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Constructs a new `Aabb` from its minimum and maximum corners
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+}
+
+/// A plane, given by the points `p` satisfying `normal · p + d = 0`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Plane {
+    /// The unit normal of the plane
+    pub normal: Vec3,
+    /// The negated distance of the plane from the origin along `normal`
+    pub d: f32,
+}
+
+impl Plane {
+    /// Constructs a new `Plane` from its unit normal and offset
+    pub fn new(normal: Vec3, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    /// Returns the signed distance from the plane to `p`, positive on the side `normal` points to
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        self.normal.dot_mul(p) + self.d
+    }
+}
+
+/// A half-line starting at `origin` and extending along `direction`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ray {
+    /// The point the ray starts from
+    pub origin: Vec3,
+    /// The unit direction the ray extends in
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Constructs a new `Ray`
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Returns the point at distance `t` along the ray
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+}
+
+/// How a shape relates to a region such as a [`Frustum`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Intersection {
+    /// The shape lies entirely inside the region
+    Inside,
+    /// The shape lies entirely outside the region
+    Outside,
+    /// The shape straddles the boundary of the region
+    Intersecting,
+}
+
+/// A view frustum, described by six planes with normals pointing inwards
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Frustum {
+    /// The left, right, bottom, top, near and far planes, in that order
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extracts the frustum of a view-projection matrix.
+    ///
+    /// Uses the Gribb-Hartmann method, which combines the rows of `view_proj`, so it works for
+    /// both perspective and orthographic projections onto an OpenGL-style `[-1, 1]` depth range.
+    /// The planes are normalized, so [`Plane::signed_distance`] gives true distances.
+    pub fn from_view_proj(view_proj: &Mat4) -> Self {
+        let [r0, r1, r2, r3] = view_proj
+            .transpose()
+            .to_cols_array_2d()
+            .map(|[x, y, z, w]| Vec4::new(x, y, z, w));
+        let plane = |v: Vec4| {
+            let normal = Vec3::new(v.x, v.y, v.z);
+            let inv = 1.0 / normal.mag();
+            Plane::new(normal * inv, v.w * inv)
+        };
+
+        Self {
+            planes: [
+                plane(r3 + r0),
+                plane(r3 - r0),
+                plane(r3 + r1),
+                plane(r3 - r1),
+                plane(r3 + r2),
+                plane(r3 - r2),
+            ],
+        }
+    }
+
+    /// Returns `true` if the sphere at `center` with `radius` is at least partly inside the
+    /// frustum.
+    ///
+    /// Like most culling tests this is conservative: spheres just outside a corner of the frustum
+    /// may still be reported as visible.
+    pub fn contains_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|p| p.signed_distance(center) >= -radius)
+    }
+
+    /// Classifies `aabb` as inside, outside or straddling the frustum.
+    ///
+    /// Each plane is tested against the box corner furthest along its normal and the one furthest
+    /// against it. As with [`Frustum::contains_sphere`], boxes near the corners of the frustum may
+    /// be reported as [`Intersection::Intersecting`] when they are really outside.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> Intersection {
+        let mut result = Intersection::Inside;
+
+        for p in &self.planes {
+            let corner = |toward: bool| {
+                let pick =
+                    |n: f32, min: f32, max: f32| if (n >= 0.0) == toward { max } else { min };
+                Vec3::new(
+                    pick(p.normal.x, aabb.min.x, aabb.max.x),
+                    pick(p.normal.y, aabb.min.y, aabb.max.y),
+                    pick(p.normal.z, aabb.min.z, aabb.max.z),
+                )
+            };
+
+            if p.signed_distance(corner(true)) < 0.0 {
+                return Intersection::Outside;
+            }
+            if p.signed_distance(corner(false)) < 0.0 {
+                result = Intersection::Intersecting;
+            }
+        }
+
+        result
+    }
+}
+
+/// The number of bits each axis is quantized to by [`morton_encode`]
+pub const MORTON_BITS_PER_AXIS: u32 = 21;
+
+const MORTON_CELLS: f32 = (1u32 << MORTON_BITS_PER_AXIS) as f32;
+
+/// Spreads the low 21 bits of `v` out so there are two zero bits between each of them
+fn morton_spread(v: u32) -> u64 {
+    let mut x = u64::from(v) & 0x1f_ffff;
+    x = (x | x << 32) & 0x001f_0000_0000_ffff;
+    x = (x | x << 16) & 0x001f_0000_ff00_00ff;
+    x = (x | x << 8) & 0x100f_00f0_0f00_f00f;
+    x = (x | x << 4) & 0x10c3_0c30_c30c_30c3;
+    x = (x | x << 2) & 0x1249_2492_4924_9249;
+    x
+}
+
+/// The inverse of [`morton_spread`]
+fn morton_compact(v: u64) -> u32 {
+    let mut x = v & 0x1249_2492_4924_9249;
+    x = (x ^ (x >> 2)) & 0x10c3_0c30_c30c_30c3;
+    x = (x ^ (x >> 4)) & 0x100f_00f0_0f00_f00f;
+    x = (x ^ (x >> 8)) & 0x001f_0000_ff00_00ff;
+    x = (x ^ (x >> 16)) & 0x001f_0000_0000_ffff;
+    x = (x ^ (x >> 32)) & 0x1f_ffff;
+    x as u32
+}
+
+/// Computes the Morton (Z-order) code of `p` within `bounds`.
+///
+/// Each axis is quantized to [`MORTON_BITS_PER_AXIS`] bits, so `bounds` is split into a grid of
+/// 2^21 cells per axis. Points outside `bounds` are clamped to its faces. The bits of the three
+/// cell coordinates are interleaved as `...zyxzyx`, giving a 63-bit code in which nearby points
+/// mostly have nearby codes.
+pub fn morton_encode(p: Vec3, bounds: &Aabb) -> u64 {
+    let size = bounds.max - bounds.min;
+    let quantize = |v: f32, min: f32, size: f32| {
+        (((v - min) / size).clamp(0.0, 1.0) * MORTON_CELLS).min(MORTON_CELLS - 1.0) as u32
+    };
+
+    morton_spread(quantize(p.x, bounds.min.x, size.x))
+        | morton_spread(quantize(p.y, bounds.min.y, size.y)) << 1
+        | morton_spread(quantize(p.z, bounds.min.z, size.z)) << 2
+}
+
+/// Recovers the center of the grid cell a Morton code produced by [`morton_encode`] refers to.
+///
+/// The result is within half a cell (`size / 2^22` per axis) of the originally encoded point.
+pub fn morton_decode(code: u64, bounds: &Aabb) -> Vec3 {
+    let size = bounds.max - bounds.min;
+    let dequantize = |i: u32, min: f32, size: f32| min + (i as f32 + 0.5) / MORTON_CELLS * size;
+
+    Vec3::new(
+        dequantize(morton_compact(code), bounds.min.x, size.x),
+        dequantize(morton_compact(code >> 1), bounds.min.y, size.y),
+        dequantize(morton_compact(code >> 2), bounds.min.z, size.z),
+    )
+}
+
+/// Finds the point on the triangle `abc` that is closest to `p`.
+///
+/// Determines which Voronoi region (vertex, edge or face) of the triangle `p` lies in and
+/// projects it onto the corresponding feature.
+pub fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot_mul(ap);
+    let d2 = ac.dot_mul(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot_mul(bp);
+    let d4 = ac.dot_mul(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot_mul(cp);
+    let d6 = ac.dot_mul(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Finds the point on or inside the tetrahedron `abcd` that is closest to `p`.
+///
+/// Points inside the tetrahedron are returned unchanged. Otherwise each face that has `p` on its
+/// outer side is searched with [`closest_point_on_triangle`], which covers the face, edge and
+/// vertex regions, and the nearest result wins. The vertices may be in either winding order, but
+/// must not be coplanar.
+pub fn closest_point_on_tetrahedron(p: Vec3, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Vec3 {
+    // `p` is outside face `abc` if it lies on the other side of it from the remaining vertex
+    let outside = |a: Vec3, b: Vec3, c: Vec3, opposite: Vec3| {
+        let n = (b - a) * (c - a);
+        (p - a).dot_mul(n) * (opposite - a).dot_mul(n) < 0.0
+    };
+
+    [(a, b, c, d), (a, c, d, b), (a, d, b, c), (b, d, c, a)]
+        .into_iter()
+        .filter(|&(a, b, c, opposite)| outside(a, b, c, opposite))
+        .map(|(a, b, c, _)| closest_point_on_triangle(p, a, b, c))
+        .min_by(|x, y| {
+            let dx = (*x - p).dot_mul(*x - p);
+            let dy = (*y - p).dot_mul(*y - p);
+            dx.total_cmp(&dy)
+        })
+        .unwrap_or(p)
+}
+
+/// Computes the signed area of the triangle `abc`, which is assumed to lie in the plane with unit
+/// normal `plane_normal`.
+///
+/// The result is positive when the vertices wind counter-clockwise when viewed from the side
+/// `plane_normal` points towards, and negative when they wind clockwise.
+pub fn signed_area_2d(a: Vec3, b: Vec3, c: Vec3, plane_normal: Vec3) -> f32 {
+    0.5 * ((b - a) * (c - a)).dot_mul(plane_normal)
+}
+
+/// The `w` value of the plane [`clip_segment_near`] clips against
+pub const CLIP_W_EPSILON: f32 = 1e-5;
+
+/// Clips a clip-space line segment against the `w = CLIP_W_EPSILON` plane, discarding the part
+/// that lies behind the camera.
+///
+/// Points with `w <= 0` would flip or blow up during the perspective divide. Since clip-space
+/// coordinates are linear along the segment, the crossing point is at
+/// `t = (a.w - ε) / (a.w - b.w)` and is found by interpolating all four components. Returns
+/// `None` if the whole segment is behind the plane.
+pub fn clip_segment_near(a: Vec4, b: Vec4) -> Option<(Vec4, Vec4)> {
+    let da = a.w - CLIP_W_EPSILON;
+    let db = b.w - CLIP_W_EPSILON;
+
+    match (da >= 0.0, db >= 0.0) {
+        (true, true) => Some((a, b)),
+        (false, false) => None,
+        (true, false) => Some((a, a.lerp(b, da / (da - db)))),
+        (false, true) => Some((a.lerp(b, da / (da - db)), b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{vec3, vec4};
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.max_abs_diff(b) < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn closest_point_on_triangle_regions() {
+        let (a, b, c) = (
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+
+        // Face
+        assert_near(
+            closest_point_on_triangle(vec3(0.25, 0.25, 1.0), a, b, c),
+            vec3(0.25, 0.25, 0.0),
+        );
+        // Edges
+        assert_near(
+            closest_point_on_triangle(vec3(0.5, -1.0, 0.0), a, b, c),
+            vec3(0.5, 0.0, 0.0),
+        );
+        assert_near(
+            closest_point_on_triangle(vec3(1.0, 1.0, 0.0), a, b, c),
+            vec3(0.5, 0.5, 0.0),
+        );
+        // Vertices
+        assert_near(closest_point_on_triangle(vec3(2.0, -1.0, 0.0), a, b, c), b);
+        assert_near(closest_point_on_triangle(vec3(-1.0, -1.0, 0.0), a, b, c), a);
+    }
+
+    #[test]
+    fn signed_area_2d_follows_winding() {
+        let (a, b, c) = (
+            vec3(0.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 0.0),
+            vec3(0.0, 2.0, 0.0),
+        );
+        let up = vec3(0.0, 0.0, 1.0);
+
+        assert_eq!(signed_area_2d(a, b, c, up), 2.0);
+        assert_eq!(signed_area_2d(a, c, b, up), -2.0);
+        assert_eq!(signed_area_2d(a, b, c, up * -1.0), -2.0);
+    }
+
+    #[test]
+    fn morton_round_trip_and_locality() {
+        let bounds = Aabb::new(vec3(-10.0, -10.0, -10.0), vec3(10.0, 10.0, 10.0));
+        let half_cell = 20.0 / (1u32 << (MORTON_BITS_PER_AXIS + 1)) as f32;
+
+        let p = vec3(1.25, -3.5, 7.75);
+        let decoded = morton_decode(morton_encode(p, &bounds), &bounds);
+        assert!(decoded.max_abs_diff(p) <= half_cell);
+
+        // Neighbouring points share a long code prefix, distant ones don't
+        let near = morton_encode(p + 1.0e-3, &bounds);
+        let far = morton_encode(p * -1.0, &bounds);
+        let code = morton_encode(p, &bounds);
+        assert!((code ^ near).leading_zeros() > (code ^ far).leading_zeros());
+
+        assert_eq!(morton_encode(bounds.min, &bounds), 0);
+        assert_eq!(morton_encode(bounds.max, &bounds), (1 << 63) - 1);
+    }
+
+    #[test]
+    fn clip_segment_near_cases() {
+        let (a, b) = (vec4(0.0, 0.0, 0.0, 1.0), vec4(1.0, 2.0, 3.0, 2.0));
+        assert_eq!(clip_segment_near(a, b), Some((a, b)));
+
+        let behind = vec4(1.0, 1.0, 1.0, -1.0);
+        assert_eq!(clip_segment_near(behind, behind * 2.0), None);
+
+        let (clipped_a, clipped_b) =
+            clip_segment_near(a, vec4(2.0, 0.0, 0.0, -1.0)).expect("segment crosses the plane");
+        assert_eq!(clipped_a, a);
+        assert!((clipped_b.w - CLIP_W_EPSILON).abs() < 1e-6);
+        assert!((clipped_b.x - 1.0).abs() < 1e-4);
+
+        let (clipped_a, clipped_b) =
+            clip_segment_near(vec4(2.0, 0.0, 0.0, -1.0), a).expect("segment crosses the plane");
+        assert_eq!(clipped_b, a);
+        assert!((clipped_a.w - CLIP_W_EPSILON).abs() < 1e-6);
+    }
+
+    #[test]
+    fn frustum_classifies_boxes_and_spheres() {
+        // Looking down -Z from the origin, with a 90 degree field of view
+        let proj = crate::mat::perspective(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+        let frustum = Frustum::from_view_proj(&proj);
+
+        let inside = Aabb::new(vec3(-1.0, -1.0, -11.0), vec3(1.0, 1.0, -9.0));
+        let outside = Aabb::new(vec3(-1.0, -1.0, 9.0), vec3(1.0, 1.0, 11.0));
+        let straddling = Aabb::new(vec3(-1.0, -1.0, -101.0), vec3(1.0, 1.0, -99.0));
+        assert_eq!(frustum.intersects_aabb(&inside), Intersection::Inside);
+        assert_eq!(frustum.intersects_aabb(&outside), Intersection::Outside);
+        assert_eq!(
+            frustum.intersects_aabb(&straddling),
+            Intersection::Intersecting
+        );
+
+        assert!(frustum.contains_sphere(vec3(0.0, 0.0, -10.0), 1.0));
+        assert!(frustum.contains_sphere(vec3(10.5, 0.0, -10.0), 1.0));
+        assert!(!frustum.contains_sphere(vec3(20.0, 0.0, -10.0), 1.0));
+    }
+
+    #[test]
+    fn closest_point_on_tetrahedron_regions() {
+        let (a, b, c, d) = (
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            vec3(0.0, 0.0, 1.0),
+        );
+        let third = 1.0 / 3.0;
+        let cases = [
+            // Inside
+            (vec3(0.1, 0.2, 0.3), vec3(0.1, 0.2, 0.3)),
+            // Face bcd
+            (vec3(1.0, 1.0, 1.0), vec3(third, third, third)),
+            // Edge bc
+            (vec3(1.0, 1.0, -1.0), vec3(0.5, 0.5, 0.0)),
+            // Vertex a
+            (vec3(-1.0, -1.0, -1.0), a),
+        ];
+
+        for (p, expected) in cases {
+            assert_near(closest_point_on_tetrahedron(p, a, b, c, d), expected);
+            assert_near(closest_point_on_tetrahedron(p, a, c, b, d), expected);
+        }
+    }
+}
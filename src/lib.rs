@@ -31,7 +31,81 @@ pub trait AsArray {
     fn as_array(&self) -> impl AsRef<[Self::Output]>;
 }
 
+/// The version byte that starts every binary encoding produced by `to_bytes`
+pub const WIRE_FORMAT_VERSION: u8 = 1;
+
+/// The error returned when decoding an object with `from_bytes` fails
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FromBytesError {
+    /// The input does not have the length of the encoded type
+    InvalidLength {
+        /// The length of a valid encoding
+        expected: usize,
+        /// The length of the input
+        actual: usize,
+    },
+    /// The input starts with a version byte this version of the crate can't decode
+    UnsupportedVersion(u8),
+}
+
+impl std::fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength { expected, actual } => {
+                write!(f, "expected {expected} bytes but got {actual}")
+            }
+            Self::UnsupportedVersion(v) => write!(f, "unsupported wire format version {v}"),
+        }
+    }
+}
+
+impl std::error::Error for FromBytesError {}
+
+/// Encodes `values` as the version byte followed by each value in little-endian order
+pub(crate) fn encode_f32s(values: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + 4 * values.len());
+    bytes.push(WIRE_FORMAT_VERSION);
+    for v in values {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// Decodes exactly `N` values from an encoding produced by [`encode_f32s`]
+pub(crate) fn decode_f32s<const N: usize>(bytes: &[u8]) -> Result<[f32; N], FromBytesError> {
+    let expected = 1 + 4 * N;
+    if bytes.len() != expected {
+        return Err(FromBytesError::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        });
+    }
+    if bytes[0] != WIRE_FORMAT_VERSION {
+        return Err(FromBytesError::UnsupportedVersion(bytes[0]));
+    }
+
+    let mut values = [0.0; N];
+    for (v, chunk) in values.iter_mut().zip(bytes[1..].chunks_exact(4)) {
+        *v = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    Ok(values)
+}
+
+/// Colors
+pub mod color;
+/// Geometric primitives and queries
+pub mod geom;
 /// Matrices
 pub mod mat;
+/// Procedural noise
+pub mod noise;
+/// Quaternions
+pub mod quat;
+/// Splines and paths
+pub mod spline;
+/// Transforms
+pub mod transform;
 /// Vectors
 pub mod vec;
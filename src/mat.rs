@@ -1,5 +1,8 @@
-use super::vec::Dot;
-use super::{Vec3, Vec4};
+use super::geom::{Aabb, Plane, Ray};
+use super::quat::Quat;
+use super::transform::Transform;
+use super::vec::{Dot, Magnitude};
+use super::{Vec2, Vec3, Vec4};
 
 /// Matrices that can be transposed
 pub trait Transpose {
@@ -7,6 +10,39 @@ pub trait Transpose {
     fn transpose(self) -> Self;
 }
 
+fn xyz(v: Vec4) -> Vec3 {
+    Vec3::new(v.x, v.y, v.z)
+}
+
+/// The matrix that negates z, converting between right- and left-handed coordinates
+fn flip_z() -> Mat4 {
+    Mat4 {
+        c0: Vec4::new(1.0, 0.0, 0.0, 0.0),
+        c1: Vec4::new(0.0, 1.0, 0.0, 0.0),
+        c2: Vec4::new(0.0, 0.0, -1.0, 0.0),
+        c3: Vec4::new(0.0, 0.0, 0.0, 1.0),
+    }
+}
+
+/// Computes the 2x2 minors used by the determinant and inverse: `s` from the first two columns
+/// and `c` from the last two, each over the row pairs `(0, 1), (0, 2), (0, 3), (1, 2), (1, 3)`
+/// and `(2, 3)`
+fn minors(a: &[[f32; 4]; 4]) -> ([f32; 6], [f32; 6]) {
+    let minor =
+        |c0: usize, c1: usize, r0: usize, r1: usize| a[c0][r0] * a[c1][r1] - a[c0][r1] * a[c1][r0];
+    let pairs = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+
+    (
+        pairs.map(|(r0, r1)| minor(0, 1, r0, r1)),
+        pairs.map(|(r0, r1)| minor(2, 3, r0, r1)),
+    )
+}
+
+/// Computes the determinant by Laplace expansion along the first two columns
+fn det_from_minors(s: &[f32; 6], c: &[f32; 6]) -> f32 {
+    s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+}
+
 /// A 4x4 matrix in column-major order
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Mat4 {
@@ -16,6 +52,631 @@ pub struct Mat4 {
     c3: Vec4,
 }
 
+impl Mat4 {
+    /// Creates a matrix from a nested array in column-major order, so `cols[i]` is the `i`th
+    /// column
+    pub fn from_cols_array_2d(cols: [[f32; 4]; 4]) -> Self {
+        let [c0, c1, c2, c3] = cols.map(|[x, y, z, w]| Vec4::new(x, y, z, w));
+        Self { c0, c1, c2, c3 }
+    }
+
+    /// Returns the elements of `self` as a nested array in column-major order, so `result[i]` is
+    /// the `i`th column
+    pub fn to_cols_array_2d(&self) -> [[f32; 4]; 4] {
+        [self.c0, self.c1, self.c2, self.c3].map(|c| [c.x, c.y, c.z, c.w])
+    }
+
+    /// Builds the affine transform that scales by `scale`, then rotates by `rotation` and finally
+    /// translates by `translation`
+    pub fn from_scale_rotation_translation(scale: Vec3, rotation: Quat, translation: Vec3) -> Self {
+        let [x, y, z] = rotation.to_basis();
+        Self {
+            c0: Vec4::new(x.x, x.y, x.z, 0.0) * scale.x,
+            c1: Vec4::new(y.x, y.y, y.z, 0.0) * scale.y,
+            c2: Vec4::new(z.x, z.y, z.z, 0.0) * scale.z,
+            c3: Vec4::new(translation.x, translation.y, translation.z, 1.0),
+        }
+    }
+
+    /// Returns the elements of `self` laid out for `gl.uniformMatrix4fv(location, transpose, data)`.
+    ///
+    /// Pass the same `transpose` flag to both calls. With `false` (the only value WebGL 1
+    /// accepts) the data is column-major, as GLSL expects. With `true` the data is row-major
+    /// instead, and WebGL 2 transposes it back while uploading, so the shader sees the same
+    /// matrix either way.
+    pub fn for_gl_uniform(&self, transpose: bool) -> [f32; 16] {
+        let m = if transpose { self.transpose() } else { *self };
+        let mut data = [0.0; 16];
+        for (dst, src) in data.chunks_exact_mut(4).zip([m.c0, m.c1, m.c2, m.c3]) {
+            dst.copy_from_slice(&[src.x, src.y, src.z, src.w]);
+        }
+
+        data
+    }
+
+    /// Encodes `self` for binary transport.
+    ///
+    /// The format is the [`crate::WIRE_FORMAT_VERSION`] byte followed by the 16 elements in
+    /// column-major order as little-endian `f32`s, 65 bytes in total.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::encode_f32s(self.to_cols_array_2d().as_flattened())
+    }
+
+    /// Decodes a matrix encoded by [`Mat4::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::FromBytesError> {
+        let e: [f32; 16] = crate::decode_f32s(bytes)?;
+        Ok(Self::from_cols_array_2d([
+            [e[0], e[1], e[2], e[3]],
+            [e[4], e[5], e[6], e[7]],
+            [e[8], e[9], e[10], e[11]],
+            [e[12], e[13], e[14], e[15]],
+        ]))
+    }
+
+    /// Builds a 2D affine transform in the xy-plane, leaving z untouched.
+    ///
+    /// The transform scales first, then rotates counter-clockwise by `rotation_rad` and finally
+    /// translates, i.e. `T * R * S`.
+    pub fn from_affine2d(scale: Vec2, rotation_rad: f32, translation: Vec2) -> Self {
+        let (sin, cos) = rotation_rad.sin_cos();
+        Self {
+            c0: Vec4::new(cos * scale.x, sin * scale.x, 0.0, 0.0),
+            c1: Vec4::new(-sin * scale.y, cos * scale.y, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, 1.0, 0.0),
+            c3: Vec4::new(translation.x, translation.y, 0.0, 1.0),
+        }
+    }
+
+    /// Splits an affine transform into its `(scale, rotation, translation)` parts.
+    ///
+    /// This is the inverse of [`Mat4::from_scale_rotation_translation`]. A negative determinant
+    /// is attributed to the x scale. The result is meaningless for matrices containing shear,
+    /// projection or a zero scale.
+    pub fn decompose(&self) -> (Vec3, Quat, Vec3) {
+        let (x, y, z) = (xyz(self.c0), xyz(self.c1), xyz(self.c2));
+
+        let det = x.dot_mul(y * z);
+        let scale = Vec3::new(if det < 0.0 { -x.mag() } else { x.mag() }, y.mag(), z.mag());
+        let rotation = Quat::from_basis(
+            x * (1.0 / scale.x),
+            y * (1.0 / scale.y),
+            z * (1.0 / scale.z),
+        );
+        (scale, rotation.normalize(), xyz(self.c3))
+    }
+
+    /// Decomposes the affine transform `self` into a [`Transform`], the inverse of
+    /// [`Transform::matrix`].
+    ///
+    /// This is [`Mat4::decompose`] with named parts, and has the same restrictions.
+    pub fn to_transform(&self) -> Transform {
+        let (scale, rotation, translation) = self.decompose();
+        Transform::new(translation, rotation, scale)
+    }
+
+    /// Returns `self` with the scale removed, by normalizing each of the first three columns.
+    ///
+    /// Translation and the bottom row are kept as is, so this is cheaper than decomposing and
+    /// recomposing. A negative scale is kept as a reflection. If `self` contains shear the columns
+    /// aren't orthogonal, and normalizing them leaves the shear in place; use
+    /// [`Mat4::rotation_quat`] to get a clean rotation instead.
+    pub fn without_scale(&self) -> Mat4 {
+        let unit = |c: Vec4| {
+            let inv = 1.0 / xyz(c).mag();
+            Vec4::new(c.x * inv, c.y * inv, c.z * inv, c.w)
+        };
+
+        Mat4 {
+            c0: unit(self.c0),
+            c1: unit(self.c1),
+            c2: unit(self.c2),
+            c3: self.c3,
+        }
+    }
+
+    /// Extracts the rotation of `self` as a unit quaternion, tolerating mild skew or numerical
+    /// drift in the upper-left 3x3 part.
+    ///
+    /// The local X axis is kept as is, Y is made orthogonal to it by Gram-Schmidt and Z is
+    /// rebuilt from the two, so the result is always a valid rotation. Scale is ignored, but a
+    /// mirrored basis comes out as the rotation with Z flipped.
+    pub fn rotation_quat(&self) -> Quat {
+        let x = xyz(self.c0).normalize();
+        let y = xyz(self.c1);
+        let y = (y - x * x.dot_mul(y)).normalize();
+        let z = x * y;
+
+        Quat::from_basis(x, y, z).normalize()
+    }
+
+    /// Returns whether the upper-left 3x3 part of `self` has a positive determinant.
+    ///
+    /// A `false` result means the transform mirrors geometry, so triangle winding order is
+    /// reversed and front/back face culling has to be flipped.
+    pub fn preserves_orientation(&self) -> bool {
+        xyz(self.c0).dot_mul(xyz(self.c1) * xyz(self.c2)) > 0.0
+    }
+
+    /// Returns the normalized local +X axis of the transform `self`
+    pub fn right(&self) -> Vec3 {
+        xyz(self.c0).normalize()
+    }
+
+    /// Returns the normalized local +Y axis of the transform `self`
+    pub fn up(&self) -> Vec3 {
+        xyz(self.c1).normalize()
+    }
+
+    /// Returns the normalized forward direction of the transform `self`.
+    ///
+    /// Following the OpenGL convention used by [`look_to`] and [`transform_look`], forward is the
+    /// local -Z axis.
+    pub fn forward(&self) -> Vec3 {
+        xyz(self.c2).normalize() * -1.0
+    }
+
+    /// Formats `self` as an aligned 4x4 grid with `precision` decimal places, for debugging.
+    ///
+    /// Unlike the storage order, the grid is laid out the way matrices are written by hand: each
+    /// line is a row, so the translation of an affine transform appears in the last column.
+    pub fn pretty(&self, precision: usize) -> String {
+        let cols = self
+            .to_cols_array_2d()
+            .map(|c| c.map(|e| format!("{e:.precision$}")));
+        let width = cols
+            .as_flattened()
+            .iter()
+            .map(String::len)
+            .max()
+            .unwrap_or(0);
+
+        (0..4)
+            .map(|r| {
+                let row = cols
+                    .iter()
+                    .map(|c| format!("{:>width$}", c[r]))
+                    .collect::<Vec<_>>();
+                format!("[ {} ]", row.join("  "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Computes the determinant of `self`.
+    ///
+    /// When built with `-C target-feature=+simd128`, the 2x2 minors of the first two columns and
+    /// of the last two are computed four at a time, lined up so that each lane multiplies a minor
+    /// by its complementary one. Without that flag the SIMD intrinsics can't be inlined and are
+    /// much slower than plain arithmetic, so the minors are computed one at a time instead.
+    pub fn determinant(&self) -> f32 {
+        #[cfg(target_feature = "simd128")]
+        {
+            let c0 = std::arch::wasm32::f32x4(self.c0.x, self.c0.y, self.c0.z, self.c0.w);
+            let c1 = std::arch::wasm32::f32x4(self.c1.x, self.c1.y, self.c1.z, self.c1.w);
+            let c2 = std::arch::wasm32::f32x4(self.c2.x, self.c2.y, self.c2.z, self.c2.w);
+            let c3 = std::arch::wasm32::f32x4(self.c3.x, self.c3.y, self.c3.z, self.c3.w);
+
+            // Row pairs (0, 1), (0, 2), (0, 3), (1, 2) of the first two columns against (2, 3),
+            // (1, 3), (1, 2), (0, 3) of the last two
+            let s_lo = std::arch::wasm32::f32x4_sub(
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<0, 0, 0, 1>(c0, c0),
+                    std::arch::wasm32::i32x4_shuffle::<1, 2, 3, 2>(c1, c1),
+                ),
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<1, 2, 3, 2>(c0, c0),
+                    std::arch::wasm32::i32x4_shuffle::<0, 0, 0, 1>(c1, c1),
+                ),
+            );
+            let c_lo = std::arch::wasm32::f32x4_sub(
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<2, 1, 1, 0>(c2, c2),
+                    std::arch::wasm32::i32x4_shuffle::<3, 3, 2, 3>(c3, c3),
+                ),
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<3, 3, 2, 3>(c2, c2),
+                    std::arch::wasm32::i32x4_shuffle::<2, 1, 1, 0>(c3, c3),
+                ),
+            );
+
+            // Row pairs (1, 3), (2, 3) against (0, 2), (0, 1) in the low two lanes
+            let s_hi = std::arch::wasm32::f32x4_sub(
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<1, 2, 1, 2>(c0, c0),
+                    std::arch::wasm32::i32x4_shuffle::<3, 3, 3, 3>(c1, c1),
+                ),
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<3, 3, 3, 3>(c0, c0),
+                    std::arch::wasm32::i32x4_shuffle::<1, 2, 1, 2>(c1, c1),
+                ),
+            );
+            let c_hi = std::arch::wasm32::f32x4_sub(
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<0, 0, 0, 0>(c2, c2),
+                    std::arch::wasm32::i32x4_shuffle::<2, 1, 2, 1>(c3, c3),
+                ),
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::i32x4_shuffle::<2, 1, 2, 1>(c2, c2),
+                    std::arch::wasm32::i32x4_shuffle::<0, 0, 0, 0>(c3, c3),
+                ),
+            );
+
+            let lo = std::arch::wasm32::f32x4_mul(
+                std::arch::wasm32::f32x4_mul(s_lo, c_lo),
+                std::arch::wasm32::f32x4(1.0, -1.0, 1.0, 1.0),
+            );
+            let hi = std::arch::wasm32::f32x4_mul(s_hi, c_hi);
+
+            std::arch::wasm32::f32x4_extract_lane::<0>(lo)
+                + std::arch::wasm32::f32x4_extract_lane::<1>(lo)
+                + std::arch::wasm32::f32x4_extract_lane::<2>(lo)
+                + std::arch::wasm32::f32x4_extract_lane::<3>(lo)
+                - std::arch::wasm32::f32x4_extract_lane::<0>(hi)
+                + std::arch::wasm32::f32x4_extract_lane::<1>(hi)
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            let (s, c) = minors(&self.to_cols_array_2d());
+            det_from_minors(&s, &c)
+        }
+    }
+
+    /// Computes the inverse of `self`, or `None` if it is singular
+    pub fn inverse(&self) -> Option<Mat4> {
+        let a = self.to_cols_array_2d();
+        let (s, c) = minors(&a);
+
+        let det = det_from_minors(&s, &c);
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv = 1.0 / det;
+
+        let b = [
+            [
+                (a[1][1] * c[5] - a[1][2] * c[4] + a[1][3] * c[3]) * inv,
+                (-a[0][1] * c[5] + a[0][2] * c[4] - a[0][3] * c[3]) * inv,
+                (a[3][1] * s[5] - a[3][2] * s[4] + a[3][3] * s[3]) * inv,
+                (-a[2][1] * s[5] + a[2][2] * s[4] - a[2][3] * s[3]) * inv,
+            ],
+            [
+                (-a[1][0] * c[5] + a[1][2] * c[2] - a[1][3] * c[1]) * inv,
+                (a[0][0] * c[5] - a[0][2] * c[2] + a[0][3] * c[1]) * inv,
+                (-a[3][0] * s[5] + a[3][2] * s[2] - a[3][3] * s[1]) * inv,
+                (a[2][0] * s[5] - a[2][2] * s[2] + a[2][3] * s[1]) * inv,
+            ],
+            [
+                (a[1][0] * c[4] - a[1][1] * c[2] + a[1][3] * c[0]) * inv,
+                (-a[0][0] * c[4] + a[0][1] * c[2] - a[0][3] * c[0]) * inv,
+                (a[3][0] * s[4] - a[3][1] * s[2] + a[3][3] * s[0]) * inv,
+                (-a[2][0] * s[4] + a[2][1] * s[2] - a[2][3] * s[0]) * inv,
+            ],
+            [
+                (-a[1][0] * c[3] + a[1][1] * c[1] - a[1][2] * c[0]) * inv,
+                (a[0][0] * c[3] - a[0][1] * c[1] + a[0][2] * c[0]) * inv,
+                (-a[3][0] * s[3] + a[3][1] * s[1] - a[3][2] * s[0]) * inv,
+                (a[2][0] * s[3] - a[2][1] * s[1] + a[2][2] * s[0]) * inv,
+            ],
+        ];
+
+        Some(Mat4::from_cols_array_2d(b))
+    }
+
+    /// Inverts `self` in place, avoiding a copy in tight loops.
+    ///
+    /// Returns `false` and leaves `self` unchanged if the matrix is singular.
+    pub fn invert_in_place(&mut self) -> bool {
+        match self.inverse() {
+            Some(inv) => {
+                *self = inv;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if no element of `self` is infinite or NaN
+    pub fn is_finite(&self) -> bool {
+        self.to_cols_array_2d()
+            .as_flattened()
+            .iter()
+            .all(|e| e.is_finite())
+    }
+
+    /// Recovers the `(near, far)` clip plane distances from a perspective projection.
+    ///
+    /// The matrix is assumed to have the layout produced by [`perspective`], i.e. an OpenGL-style
+    /// projection onto a `[-1, 1]` depth range with the camera looking down -Z. Returns `None` if
+    /// `self` does not have that shape.
+    pub fn extract_near_far(&self) -> Option<(f32, f32)> {
+        if !self.is_perspective() {
+            return None;
+        }
+
+        let (a, b) = (self.c2.z, self.c3.z);
+        let near = b / (a - 1.0);
+        let far = b / (a + 1.0);
+
+        (near.is_finite() && far.is_finite() && near > 0.0 && far > near).then_some((near, far))
+    }
+
+    /// Recovers the vertical field of view, in radians, from a perspective projection.
+    ///
+    /// Like [`Mat4::extract_near_far`], this assumes the layout produced by [`perspective`] and
+    /// returns `None` for any other kind of matrix.
+    pub fn extract_fov_y(&self) -> Option<f32> {
+        (self.is_perspective() && self.c1.y > 0.0).then(|| 2.0 * (1.0 / self.c1.y).atan())
+    }
+
+    /// Computes the NDC depth that the projection `self` assigns to a point at view-space z
+    /// coordinate `view_z`.
+    ///
+    /// `view_z` is signed, so with the camera looking down -Z as in [`perspective`], points in
+    /// front of it have a negative `view_z`: `-near` maps to -1 and `-far` to 1. Sampling this
+    /// over a range of distances shows how unevenly a perspective projection spreads depth
+    /// precision, and so where z-fighting is likely.
+    pub fn ndc_depth_at(&self, view_z: f32) -> f32 {
+        let z = self.c2.z * view_z + self.c3.z;
+        let w = self.c2.w * view_z + self.c3.w;
+        z / w
+    }
+
+    /// Converts an OpenGL-style projection into its Direct3D equivalent.
+    ///
+    /// `self` is expected to take right-handed view space (camera looking down -Z) to clip space
+    /// with NDC depth in `[-1, 1]`, as [`perspective`] and [`ortho`] do. The result takes
+    /// left-handed view space (camera looking down +Z) to clip space with depth in `[0, 1]`, so
+    /// the near plane ends up at depth 0 and the far plane at 1. x, y and w are unchanged.
+    pub fn gl_to_d3d_clip(&self) -> Mat4 {
+        let remap_z = Mat4 {
+            c0: Vec4::new(1.0, 0.0, 0.0, 0.0),
+            c1: Vec4::new(0.0, 1.0, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, 0.5, 0.0),
+            c3: Vec4::new(0.0, 0.0, 0.5, 1.0),
+        };
+        remap_z * *self * flip_z()
+    }
+
+    /// Converts a Direct3D-style projection into its OpenGL equivalent, the inverse of
+    /// [`Mat4::gl_to_d3d_clip`]
+    pub fn d3d_to_gl_clip(&self) -> Mat4 {
+        let remap_z = Mat4 {
+            c0: Vec4::new(1.0, 0.0, 0.0, 0.0),
+            c1: Vec4::new(0.0, 1.0, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, 2.0, 0.0),
+            c3: Vec4::new(0.0, 0.0, -1.0, 1.0),
+        };
+        remap_z * *self * flip_z()
+    }
+
+    /// Inverts an orthographic projection in closed form.
+    ///
+    /// `self` must have been produced by [`ortho`] (a diagonal scale plus a translation); any
+    /// other matrix gives a meaningless result.
+    pub fn inverse_ortho(&self) -> Mat4 {
+        let (sx, sy, sz) = (1.0 / self.c0.x, 1.0 / self.c1.y, 1.0 / self.c2.z);
+
+        Mat4 {
+            c0: Vec4::new(sx, 0.0, 0.0, 0.0),
+            c1: Vec4::new(0.0, sy, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, sz, 0.0),
+            c3: Vec4::new(-self.c3.x * sx, -self.c3.y * sy, -self.c3.z * sz, 1.0),
+        }
+    }
+
+    /// Computes the Cholesky factorization of a symmetric positive-definite matrix, returning the
+    /// lower-triangular `L` such that `L * L.transpose() == self`.
+    ///
+    /// Only the lower triangle of `self` is read, so symmetry is assumed rather than checked.
+    /// Returns `None` if the matrix is not positive-definite.
+    pub fn cholesky(&self) -> Option<Mat4> {
+        let a = self.to_cols_array_2d();
+        let mut l = [[0.0f32; 4]; 4];
+
+        for j in 0..4 {
+            let diag = a[j][j] - (0..j).map(|k| l[k][j] * l[k][j]).sum::<f32>();
+            if diag <= 0.0 || !diag.is_finite() {
+                return None;
+            }
+            l[j][j] = diag.sqrt();
+
+            for i in j + 1..4 {
+                let off = a[j][i] - (0..j).map(|k| l[k][i] * l[k][j]).sum::<f32>();
+                l[j][i] = off / l[j][j];
+            }
+        }
+
+        Some(Mat4::from_cols_array_2d(l))
+    }
+
+    /// Computes the numerical rank of `self` by Gaussian elimination with partial pivoting.
+    ///
+    /// A pivot whose magnitude is at most `eps` is treated as zero, so `eps` decides how close to
+    /// degenerate a matrix may be before it loses rank. Pick it relative to the scale of the
+    /// elements; `0.0` only catches exactly dependent rows.
+    pub fn rank(&self, eps: f32) -> usize {
+        // Row rank equals column rank, so the columns can be reduced as if they were rows
+        let mut a = self.to_cols_array_2d();
+        let mut rank = 0;
+
+        for col in 0..4 {
+            let Some(pivot) =
+                (rank..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            else {
+                break;
+            };
+            if a[pivot][col].abs() <= eps {
+                continue;
+            }
+            a.swap(rank, pivot);
+
+            let p = a[rank];
+            for row in &mut a[rank + 1..] {
+                let f = row[col] / p[col];
+                for (e, pe) in row[col..].iter_mut().zip(&p[col..]) {
+                    *e -= f * pe;
+                }
+            }
+            rank += 1;
+        }
+
+        rank
+    }
+
+    /// Computes the tightest axis-aligned box containing `aabb` transformed by the affine
+    /// transform `self`.
+    ///
+    /// Uses Arvo's method: the box center is transformed as a point, and each half-extent of the
+    /// result is the sum of the input half-extents weighted by the absolute values of the
+    /// matching row of the 3x3 part. This gives the same box as transforming all eight corners at
+    /// a fraction of the cost. Projective matrices are not supported.
+    pub fn transform_aabb(&self, aabb: &Aabb) -> Aabb {
+        let center = (aabb.min + aabb.max) * 0.5;
+        let extent = (aabb.max - aabb.min) * 0.5;
+        let abs = |v: Vec4| Vec3::new(v.x.abs(), v.y.abs(), v.z.abs());
+
+        let center = xyz(self.c0) * center.x
+            + xyz(self.c1) * center.y
+            + xyz(self.c2) * center.z
+            + xyz(self.c3);
+        let extent = abs(self.c0) * extent.x + abs(self.c1) * extent.y + abs(self.c2) * extent.z;
+
+        Aabb::new(center - extent, center + extent)
+    }
+
+    /// Builds a matrix that flattens geometry onto `plane` along the direction `light_dir` of a
+    /// directional light's rays, for drawing planar shadows.
+    ///
+    /// The result is a projective matrix, so divide by `w` after transforming a point. If the
+    /// light is parallel to the plane the rays never reach it and the matrix is degenerate,
+    /// sending every point to infinity.
+    pub fn shadow_projection(plane: &Plane, light_dir: Vec3) -> Mat4 {
+        Self::shadow_projection_from(plane, Vec4::new(light_dir.x, light_dir.y, light_dir.z, 0.0))
+    }
+
+    /// Builds a matrix that projects geometry onto `plane` away from a point light at
+    /// `light_pos`, for drawing planar shadows.
+    ///
+    /// Like [`Mat4::shadow_projection`] the result needs a divide by `w`. It is degenerate when
+    /// the light lies on the plane.
+    pub fn shadow_projection_point(plane: &Plane, light_pos: Vec3) -> Mat4 {
+        Self::shadow_projection_from(plane, Vec4::new(light_pos.x, light_pos.y, light_pos.z, 1.0))
+    }
+
+    /// Computes `(P · L) I - L Pᵀ` for the plane `P` and homogeneous light `L`
+    fn shadow_projection_from(plane: &Plane, light: Vec4) -> Mat4 {
+        let p = Vec4::new(plane.normal.x, plane.normal.y, plane.normal.z, plane.d);
+        let dot = p.dot_mul(light);
+
+        Mat4 {
+            c0: Vec4::new(dot, 0.0, 0.0, 0.0) - light * p.x,
+            c1: Vec4::new(0.0, dot, 0.0, 0.0) - light * p.y,
+            c2: Vec4::new(0.0, 0.0, dot, 0.0) - light * p.z,
+            c3: Vec4::new(0.0, 0.0, 0.0, dot) - light * p.w,
+        }
+    }
+
+    /// Computes the matrix logarithm of the rigid transform `self`, its twist in se(3).
+    ///
+    /// The result has the rotation as a skew-symmetric upper-left 3x3 part (the axis scaled by
+    /// the angle) and the screw's linear velocity in the last column, with a zero bottom row.
+    /// Scaling it by `t` and applying [`Mat4::exp`] moves a fraction `t` along the screw motion.
+    ///
+    /// `self` must be a rotation plus translation, without scale or shear, and its rotation angle
+    /// must be below π: at π the axis is ambiguous and the result loses all precision. Small
+    /// angles are handled with series expansions, so the identity maps cleanly to zero.
+    pub fn log(&self) -> Mat4 {
+        let r = [xyz(self.c0), xyz(self.c1), xyz(self.c2)];
+        let cos = ((r[0].x + r[1].y + r[2].z - 1.0) * 0.5).clamp(-1.0, 1.0);
+        let theta = cos.acos();
+        let sin = theta.sin();
+
+        let axis = Vec3::new(r[1].z - r[2].y, r[2].x - r[0].z, r[0].y - r[1].x);
+        let (a, d) = if theta < 1e-3 {
+            (
+                0.5 + theta * theta / 12.0,
+                1.0 / 12.0 + theta * theta / 720.0,
+            )
+        } else {
+            (
+                theta / (2.0 * sin),
+                (1.0 - theta * sin / (2.0 * (1.0 - cos))) / (theta * theta),
+            )
+        };
+        let omega = axis * a;
+
+        let t = xyz(self.c3);
+        let v = t - (omega * t) * 0.5 + omega * (omega * t) * d;
+        Self::from_twist(omega, v)
+    }
+
+    /// Computes the matrix exponential of the twist `self`, the inverse of [`Mat4::log`].
+    ///
+    /// `self` must have the se(3) layout produced by [`Mat4::log`]; only the skew-symmetric part
+    /// above the diagonal and the last column are read. The closed-form Rodrigues expansion is
+    /// used, switching to series expansions for small angles, so it converges for any twist.
+    pub fn exp(&self) -> Mat4 {
+        let (omega, v) = self.twist();
+        let theta = omega.mag();
+        let (sin, cos) = theta.sin_cos();
+
+        let t2 = theta * theta;
+        let (a, b, c) = if theta < 1e-3 {
+            (1.0 - t2 / 6.0, 0.5 - t2 / 24.0, 1.0 / 6.0 - t2 / 120.0)
+        } else {
+            (sin / theta, (1.0 - cos) / t2, (theta - sin) / (t2 * theta))
+        };
+
+        let rotate = |e: Vec3| e + (omega * e) * a + omega * (omega * e) * b;
+        let t = v + (omega * v) * b + omega * (omega * v) * c;
+        let [x, y, z] = [
+            rotate(Vec3::new(1.0, 0.0, 0.0)),
+            rotate(Vec3::new(0.0, 1.0, 0.0)),
+            rotate(Vec3::new(0.0, 0.0, 1.0)),
+        ];
+
+        Mat4 {
+            c0: Vec4::new(x.x, x.y, x.z, 0.0),
+            c1: Vec4::new(y.x, y.y, y.z, 0.0),
+            c2: Vec4::new(z.x, z.y, z.z, 0.0),
+            c3: Vec4::new(t.x, t.y, t.z, 1.0),
+        }
+    }
+
+    /// Builds the se(3) matrix with angular part `omega` and linear part `v`
+    fn from_twist(omega: Vec3, v: Vec3) -> Mat4 {
+        Mat4 {
+            c0: Vec4::new(0.0, omega.z, -omega.y, 0.0),
+            c1: Vec4::new(-omega.z, 0.0, omega.x, 0.0),
+            c2: Vec4::new(omega.y, -omega.x, 0.0, 0.0),
+            c3: Vec4::new(v.x, v.y, v.z, 0.0),
+        }
+    }
+
+    /// Reads the angular and linear parts of an se(3) matrix, the inverse of
+    /// [`Mat4::from_twist`]
+    fn twist(&self) -> (Vec3, Vec3) {
+        (Vec3::new(self.c1.z, self.c2.x, self.c0.y), xyz(self.c3))
+    }
+
+    /// Inverts a rotation plus translation by transposing the rotation
+    fn rigid_inverse(&self) -> Mat4 {
+        let rt = Mat4 {
+            c3: Vec4::new(0.0, 0.0, 0.0, 1.0),
+            ..*self
+        }
+        .transpose();
+        let t = rt * Vec4::new(-self.c3.x, -self.c3.y, -self.c3.z, 1.0);
+
+        Mat4 { c3: t, ..rt }
+    }
+
+    fn is_perspective(&self) -> bool {
+        self.c0.w == 0.0 && self.c1.w == 0.0 && self.c2.w == -1.0 && self.c3.w == 0.0
+    }
+}
+
 impl Transpose for Mat4 {
     fn transpose(self) -> Self {
         Self {
@@ -45,25 +706,127 @@ impl std::ops::Mul for Mat4 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut cols: [Vec4; 4] = [num::zero(); 4];
-        let rhs = rhs.transpose();
-        for i in 0..4 {
-            let mut v: [f32; 4] = [0.0; 4];
-            for j in 0..4 {
-                v[j] = self[i].dot_mul(rhs[j]);
-            }
-            cols[i] = Vec4::new(v[0], v[1], v[2], v[3]);
+        let lhs = self.to_cols_array_2d();
+        let res =
+            Self::from_cols_array_2d(rhs.to_cols_array_2d().map(|col| combine_cols(&lhs, col)));
+
+        // Catch NaN/infinity where it is produced rather than as invisible geometry later on.
+        // Release builds skip the check to keep multiplication cheap.
+        debug_assert!(
+            res.is_finite(),
+            "Mat4 multiplication produced a non-finite result: {self:?} * {rhs:?}"
+        );
+
+        res
+    }
+}
+
+/// Computes `cols * v`, the linear combination of `cols` weighted by the elements of `v`.
+///
+/// The sums are accumulated with fused multiply-adds, which round once per term rather than
+/// twice and so lose noticeably less precision in products of ill-conditioned matrices. With
+/// `-C target-feature=+relaxed-simd` the relaxed-SIMD multiply-add is used instead; whether it is
+/// actually fused depends on the host CPU, so the precision benefit is best-effort there.
+fn combine_cols(cols: &[[f32; 4]; 4], v: [f32; 4]) -> [f32; 4] {
+    #[cfg(target_feature = "relaxed-simd")]
+    {
+        let mut acc = std::arch::wasm32::f32x4_splat(0.0);
+        for (col, v) in cols.iter().zip(v) {
+            let col = std::arch::wasm32::f32x4(col[0], col[1], col[2], col[3]);
+            let v = std::arch::wasm32::f32x4_splat(v);
+            acc = std::arch::wasm32::f32x4_relaxed_madd(col, v, acc);
         }
 
-        Self {
-            c0: cols[0],
-            c1: cols[1],
-            c2: cols[2],
-            c3: cols[3],
+        [
+            std::arch::wasm32::f32x4_extract_lane::<0>(acc),
+            std::arch::wasm32::f32x4_extract_lane::<1>(acc),
+            std::arch::wasm32::f32x4_extract_lane::<2>(acc),
+            std::arch::wasm32::f32x4_extract_lane::<3>(acc),
+        ]
+    }
+
+    #[cfg(not(target_feature = "relaxed-simd"))]
+    {
+        let mut res = [0.0; 4];
+        for (r, res) in res.iter_mut().enumerate() {
+            *res = (0..4).fold(0.0, |acc, k| cols[k][r].mul_add(v[k], acc));
+        }
+
+        res
+    }
+}
+
+impl std::ops::Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    /// Sums the columns of `self` weighted by the components of `rhs`.
+    ///
+    /// When built with `-C target-feature=+simd128`, each component is broadcast across all lanes
+    /// and multiplied with a whole column, so no transpose is needed. Without that flag the
+    /// intrinsics can't be inlined, so the sums are computed one component at a time instead.
+    fn mul(self, rhs: Vec4) -> Self::Output {
+        #[cfg(target_feature = "simd128")]
+        {
+            let col = |c: Vec4, v: f32| {
+                std::arch::wasm32::f32x4_mul(
+                    std::arch::wasm32::f32x4(c.x, c.y, c.z, c.w),
+                    std::arch::wasm32::f32x4_splat(v),
+                )
+            };
+            let res = std::arch::wasm32::f32x4_add(
+                std::arch::wasm32::f32x4_add(col(self.c0, rhs.x), col(self.c1, rhs.y)),
+                std::arch::wasm32::f32x4_add(col(self.c2, rhs.z), col(self.c3, rhs.w)),
+            );
+
+            Vec4::new(
+                std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                std::arch::wasm32::f32x4_extract_lane::<2>(res),
+                std::arch::wasm32::f32x4_extract_lane::<3>(res),
+            )
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            mul_vec4_lanes(&self, rhs)
         }
     }
 }
 
+/// Computes `m * v` one component at a time, summing in the same order as the SIMD version.
+///
+/// This is the fallback for `Mat4 * Vec4` without `simd128`, and the reference its SIMD version
+/// is tested against.
+#[cfg(any(test, not(target_feature = "simd128")))]
+fn mul_vec4_lanes(m: &Mat4, v: Vec4) -> Vec4 {
+    let lane = |c0: f32, c1: f32, c2: f32, c3: f32| (c0 * v.x + c1 * v.y) + (c2 * v.z + c3 * v.w);
+    Vec4::new(
+        lane(m.c0.x, m.c1.x, m.c2.x, m.c3.x),
+        lane(m.c0.y, m.c1.y, m.c2.y, m.c3.y),
+        lane(m.c0.z, m.c1.z, m.c2.z, m.c3.z),
+        lane(m.c0.w, m.c1.w, m.c2.w, m.c3.w),
+    )
+}
+
+impl std::ops::Mul<Mat4> for Vec4 {
+    type Output = Vec4;
+
+    /// Multiplies the row vector `self` by `rhs`, i.e. computes `selfᵀ * rhs`.
+    ///
+    /// This crate otherwise treats vectors as columns and transforms them as `m * v`, as GLSL
+    /// does. The row-vector product is the convention of DirectX and HLSL's `mul(v, m)`, and equals
+    /// `rhs.transpose() * self`, so a matrix built here has to be transposed for it to apply the
+    /// same transform.
+    fn mul(self, rhs: Mat4) -> Self::Output {
+        Vec4::new(
+            self.dot_mul(rhs.c0),
+            self.dot_mul(rhs.c1),
+            self.dot_mul(rhs.c2),
+            self.dot_mul(rhs.c3),
+        )
+    }
+}
+
 impl std::ops::Add for Mat4 {
     type Output = Mat4;
 
@@ -77,6 +840,21 @@ impl std::ops::Add for Mat4 {
     }
 }
 
+impl num::Zero for Mat4 {
+    fn zero() -> Self {
+        Self {
+            c0: num::zero(),
+            c1: num::zero(),
+            c2: num::zero(),
+            c3: num::zero(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero() && self.c2.is_zero() && self.c3.is_zero()
+    }
+}
+
 impl num::One for Mat4 {
     fn one() -> Self {
         Self {
@@ -91,10 +869,320 @@ impl num::One for Mat4 {
 /// Creates a new matrix corresponding to the supplied matrix composed with a translate operation
 pub fn translate(mat: &Mat4, vec: Vec3) -> Mat4 {
     Mat4 {
-        c0: Vec4::new(mat.c0.x, mat.c1.x, mat.c2.x, mat.c3.x + vec.x),
-        c1: Vec4::new(mat.c0.y, mat.c1.y, mat.c2.y, mat.c3.y + vec.y),
-        c2: Vec4::new(mat.c0.z, mat.c1.z, mat.c2.z, mat.c3.z + vec.z),
-        c3: Vec4::new(mat.c0.w, mat.c1.w, mat.c2.w, mat.c3.w),
+        c0: mat.c0,
+        c1: mat.c1,
+        c2: mat.c2,
+        c3: mat.c0 * vec.x + mat.c1 * vec.y + mat.c2 * vec.z + mat.c3,
+    }
+}
+
+/// Creates a new matrix corresponding to the supplied matrix composed with a rotation of `angle`
+/// radians about `axis`
+pub fn rotate(mat: &Mat4, angle: f32, axis: Vec3) -> Mat4 {
+    *mat * Mat4::from(Quat::from_axis_angle(axis.normalize(), angle))
+}
+
+/// Creates a rotation of `angle_rad` radians about `axis` passing through `pivot` rather than
+/// the origin, i.e. `translate(pivot) * rotate(axis) * translate(-pivot)`
+pub fn rotate_around_pivot(pivot: Vec3, axis: Vec3, angle_rad: f32) -> Mat4 {
+    let m = translate(&num::one(), pivot);
+    let m = rotate(&m, angle_rad, axis);
+    translate(&m, pivot * -1.0)
+}
+
+/// Creates a rotation matrix from Euler angles in radians.
+///
+/// The rotations are applied in the order roll (about Z), then pitch (about X), then yaw (about
+/// Y), so the result is `Ry(yaw) * Rx(pitch) * Rz(roll)`.
+pub fn from_euler_angles(pitch: f32, yaw: f32, roll: f32) -> Mat4 {
+    let m = rotate(&num::one(), yaw, Vec3::new(0.0, 1.0, 0.0));
+    let m = rotate(&m, pitch, Vec3::new(1.0, 0.0, 0.0));
+    rotate(&m, roll, Vec3::new(0.0, 0.0, 1.0))
+}
+
+/// Creates a perspective projection matrix.
+///
+/// `fov_y` is the vertical field of view in radians. View space is right-handed with the camera
+/// looking down -Z, and depths between `near` and `far` are mapped onto `[-1, 1]` as in OpenGL.
+pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+    let f = 1.0 / (fov_y * 0.5).tan();
+
+    Mat4 {
+        c0: Vec4::new(f / aspect, 0.0, 0.0, 0.0),
+        c1: Vec4::new(0.0, f, 0.0, 0.0),
+        c2: Vec4::new(0.0, 0.0, (far + near) / (near - far), -1.0),
+        c3: Vec4::new(0.0, 0.0, 2.0 * far * near / (near - far), 0.0),
+    }
+}
+
+/// Creates a perspective projection matrix with the far plane at infinity.
+///
+/// Identical to [`perspective`] in the limit `far -> ∞`: depths from `near` to infinity are
+/// mapped onto `[-1, 1)`. Use [`unproject_infinite`] to map points back to world space.
+pub fn perspective_infinite(fov_y: f32, aspect: f32, near: f32) -> Mat4 {
+    let f = 1.0 / (fov_y * 0.5).tan();
+
+    Mat4 {
+        c0: Vec4::new(f / aspect, 0.0, 0.0, 0.0),
+        c1: Vec4::new(0.0, f, 0.0, 0.0),
+        c2: Vec4::new(0.0, 0.0, -1.0, -1.0),
+        c3: Vec4::new(0.0, 0.0, -2.0 * near, 0.0),
+    }
+}
+
+/// Maps a point in normalized device coordinates back to world space using the inverse of the
+/// view-projection matrix.
+///
+/// Returns `None` if the point maps to infinity (`w == 0`).
+pub fn unproject(ndc: Vec3, inv_view_proj: &Mat4) -> Option<Vec3> {
+    let p = *inv_view_proj * Vec4::new(ndc.x, ndc.y, ndc.z, 1.0);
+    (p.w != 0.0).then(|| xyz(p) * (1.0 / p.w))
+}
+
+/// Builds the world-space picking ray through a point on the screen.
+///
+/// `ndc` is in normalized device coordinates, with x and y running from -1 to 1 left to right
+/// and bottom to top. Mouse positions in pixels have y pointing down, so convert them with
+/// `x = 2 * px / width - 1` and `y = 1 - 2 * py / height`. The point is unprojected onto the near
+/// plane with `inv_view_proj`, and the ray starts at the camera position `eye` and passes through
+/// it.
+pub fn screen_to_ray(ndc: Vec2, inv_view_proj: &Mat4, eye: Vec3) -> Ray {
+    let p = *inv_view_proj * Vec4::new(ndc.x, ndc.y, -1.0, 1.0);
+    let near = xyz(p) * (1.0 / p.w);
+
+    Ray::new(eye, (near - eye).normalize())
+}
+
+/// Maps a point in normalized device coordinates back to world space for a camera using
+/// [`perspective_infinite`] with the same `fov_y`, `aspect` and `near`.
+///
+/// Rather than inverting the full view-projection matrix, the view-space depth is recovered in
+/// closed form as `2 * near / (ndc.z - 1)`. This stays accurate as `ndc.z` approaches 1, where
+/// a generic inverse loses most of its precision. The far plane of the infinite projection,
+/// `ndc.z == 1`, lies at infinity, so `None` is returned for `ndc.z >= 1` as well as if `view`
+/// is singular.
+pub fn unproject_infinite(
+    ndc: Vec3,
+    view: &Mat4,
+    fov_y: f32,
+    aspect: f32,
+    near: f32,
+) -> Option<Vec3> {
+    if ndc.z >= 1.0 {
+        return None;
+    }
+
+    let f = 1.0 / (fov_y * 0.5).tan();
+    let z = 2.0 * near / (ndc.z - 1.0);
+    let p = Vec4::new(ndc.x * -z * aspect / f, ndc.y * -z / f, z, 1.0);
+
+    Some(xyz(view.inverse()? * p))
+}
+
+/// Creates an orthographic projection matrix.
+///
+/// The box bounded by `left`/`right`, `bottom`/`top` and the `near`/`far` planes (measured along
+/// -Z) is mapped onto `[-1, 1]` on every axis, as in OpenGL.
+pub fn ortho(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+    Mat4 {
+        c0: Vec4::new(2.0 / (right - left), 0.0, 0.0, 0.0),
+        c1: Vec4::new(0.0, 2.0 / (top - bottom), 0.0, 0.0),
+        c2: Vec4::new(0.0, 0.0, -2.0 / (far - near), 0.0),
+        c3: Vec4::new(
+            -(right + left) / (right - left),
+            -(top + bottom) / (top - bottom),
+            -(far + near) / (far - near),
+            1.0,
+        ),
+    }
+}
+
+/// Creates an orthographic projection for 2D drawing in pixel coordinates.
+///
+/// The origin is the top-left corner of a `width` by `height` screen and y grows downwards, as
+/// in the DOM and canvas APIs. This flips y relative to NDC, so `(0, 0)` maps to `(-1, 1)` and
+/// `(width, height)` to `(1, -1)`. The flip also reverses triangle winding, which matters if face
+/// culling is enabled. z in `[-1, 1]` passes through with its sign flipped, as in [`ortho`].
+pub fn ortho_2d_screen(width: f32, height: f32) -> Mat4 {
+    ortho(0.0, width, height, 0.0, -1.0, 1.0)
+}
+
+/// Computes the world-space height covered by a single pixel at `depth` units in front of the
+/// camera.
+///
+/// For a perspective projection (as built by [`perspective`]) the visible height grows linearly
+/// with `depth`. Any other projection is treated as orthographic, where the pixel size is the
+/// same at every depth and `depth` is ignored.
+pub fn world_units_per_pixel(proj: &Mat4, depth: f32, viewport_height: f32) -> f32 {
+    let visible_height = if proj.is_perspective() {
+        2.0 * depth.abs() / proj.c1.y
+    } else {
+        2.0 / proj.c1.y
+    };
+
+    visible_height / viewport_height
+}
+
+/// Creates a view matrix for a camera at `eye` looking along `dir`.
+///
+/// Following the OpenGL convention, the camera looks down its local -Z axis with `up` roughly
+/// along +Y. This is the inverse of [`transform_look`].
+pub fn look_to(eye: Vec3, dir: Vec3, up: Vec3) -> Mat4 {
+    let f = dir.normalize();
+    let s = (f * up).normalize();
+    let u = s * f;
+
+    Mat4 {
+        c0: Vec4::new(s.x, u.x, -f.x, 0.0),
+        c1: Vec4::new(s.y, u.y, -f.y, 0.0),
+        c2: Vec4::new(s.z, u.z, -f.z, 0.0),
+        c3: Vec4::new(-s.dot_mul(eye), -u.dot_mul(eye), f.dot_mul(eye), 1.0),
+    }
+}
+
+/// A face of a cube map, named after the axis it looks along
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CubeFace {
+    /// The `TEXTURE_CUBE_MAP_POSITIVE_X` face
+    PositiveX,
+    /// The `TEXTURE_CUBE_MAP_NEGATIVE_X` face
+    NegativeX,
+    /// The `TEXTURE_CUBE_MAP_POSITIVE_Y` face
+    PositiveY,
+    /// The `TEXTURE_CUBE_MAP_NEGATIVE_Y` face
+    NegativeY,
+    /// The `TEXTURE_CUBE_MAP_POSITIVE_Z` face
+    PositiveZ,
+    /// The `TEXTURE_CUBE_MAP_NEGATIVE_Z` face
+    NegativeZ,
+}
+
+/// Creates the view matrix for rendering `face` of a cube map centered at `eye`.
+///
+/// The up vectors follow the WebGL (and OpenGL) cube map convention, in which the side faces
+/// have -Y as up, +Y has +Z and -Y has -Z. Combined with a 90° [`perspective`] with an aspect
+/// ratio of 1, each face then lines up with the texels a shader samples in that direction.
+pub fn cube_face_view(face: CubeFace, eye: Vec3) -> Mat4 {
+    let (dir, up) = match face {
+        CubeFace::PositiveX => (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+        CubeFace::NegativeX => (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+        CubeFace::PositiveY => (Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+        CubeFace::NegativeY => (Vec3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 0.0, -1.0)),
+        CubeFace::PositiveZ => (Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, -1.0, 0.0)),
+        CubeFace::NegativeZ => (Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, -1.0, 0.0)),
+    };
+
+    look_to(eye, dir, up)
+}
+
+/// Creates the world (model) transform of an object at `position` facing along `forward`.
+///
+/// The object's local -Z axis is mapped onto `forward` and its local +Y axis lies in the plane
+/// spanned by `forward` and `up`, so this is the inverse of the view matrix produced by
+/// [`look_to`] with the same arguments.
+pub fn transform_look(position: Vec3, forward: Vec3, up: Vec3) -> Mat4 {
+    let f = forward.normalize();
+    let s = (f * up).normalize();
+    let u = s * f;
+
+    Mat4 {
+        c0: Vec4::new(s.x, s.y, s.z, 0.0),
+        c1: Vec4::new(u.x, u.y, u.z, 0.0),
+        c2: Vec4::new(-f.x, -f.y, -f.z, 0.0),
+        c3: Vec4::new(position.x, position.y, position.z, 1.0),
+    }
+}
+
+/// Interpolates between two transforms by decomposing them into translation, rotation and scale.
+///
+/// Translation and scale are interpolated linearly while rotation is spherically interpolated,
+/// so (unlike an element-wise blend) the result stays a rigid rotation throughout. Both `a` and
+/// `b` are assumed to be affine transforms composed as translate * rotate * scale, without shear.
+pub fn interpolate_transform(a: &Mat4, b: &Mat4, t: f32) -> Mat4 {
+    let (s0, r0, t0) = a.decompose();
+    let (s1, r1, t1) = b.decompose();
+
+    Mat4::from_scale_rotation_translation(s0 + (s1 - s0) * t, r0.slerp(r1, t), t0 + (t1 - t0) * t)
+}
+
+/// Interpolates between two rigid transforms along the screw motion connecting them.
+///
+/// The relative transform from `a` to `b` is taken to se(3) with [`Mat4::log`], scaled by `t` and
+/// mapped back with [`Mat4::exp`]. Unlike [`interpolate_transform`], which moves the origin in a
+/// straight line, every point then travels at constant speed along a helix, rotating and
+/// translating together. Both transforms must be rigid (no scale or shear), and the rotation
+/// between them must be below π.
+pub fn screw_interpolate(a: &Mat4, b: &Mat4, t: f32) -> Mat4 {
+    let (omega, v) = (a.rigid_inverse() * *b).log().twist();
+    *a * Mat4::from_twist(omega * t, v * t).exp()
+}
+
+/// Computes `out[i] = mats[i].inverse()` for every element.
+///
+/// This is only a loop over [`Mat4::inverse`] with no shared setup between elements, so singular
+/// matrices produce `None` exactly as they would there.
+///
+/// # Panics
+///
+/// Panics if `mats` and `out` have different lengths.
+pub fn inverse_batch(mats: &[Mat4], out: &mut [Option<Mat4>]) {
+    assert_eq!(
+        mats.len(),
+        out.len(),
+        "Every matrix needs exactly one output slot"
+    );
+
+    for (m, out) in mats.iter().zip(out) {
+        *out = m.inverse();
+    }
+}
+
+/// Blends any number of transforms by weight.
+///
+/// Each transform is decomposed as in [`interpolate_transform`]. Translations and scales are
+/// averaged by weight, while rotations are accumulated as quaternions (flipped into a common
+/// hemisphere) and normalized at the end. Weights that don't sum to one are renormalized, and the
+/// identity is returned if there is nothing to blend or the weights sum to zero.
+///
+/// # Panics
+///
+/// Panics if `transforms` and `weights` have different lengths.
+pub fn blend_transforms(transforms: &[Mat4], weights: &[f32]) -> Mat4 {
+    assert_eq!(
+        transforms.len(),
+        weights.len(),
+        "Every transform needs exactly one weight"
+    );
+
+    let total: f32 = weights.iter().sum();
+    if total.abs() <= f32::EPSILON {
+        return num::one();
+    }
+
+    let mut scale: Vec3 = num::zero();
+    let mut rotation = Quat::new(0.0, 0.0, 0.0, 0.0);
+    let mut translation: Vec3 = num::zero();
+    for (m, &w) in transforms.iter().zip(weights) {
+        let w = w / total;
+        let (s, r, t) = m.decompose();
+        let w_r = if rotation.dot_mul(r) < 0.0 { -w } else { w };
+
+        scale = scale + s * w;
+        rotation = Quat::new(
+            rotation.x + r.x * w_r,
+            rotation.y + r.y * w_r,
+            rotation.z + r.z * w_r,
+            rotation.w + r.w * w_r,
+        );
+        translation = translation + t * w;
+    }
+
+    Mat4::from_scale_rotation_translation(scale, rotation.normalize(), translation)
+}
+
+impl From<Quat> for Mat4 {
+    fn from(value: Quat) -> Self {
+        Self::from_scale_rotation_translation(num::one(), value, num::zero())
     }
 }
 
@@ -113,3 +1201,973 @@ impl crate::AsArray for Mat4 {
             .collect::<Vec<_>>()
     }
 }
+
+/// A 3x3 matrix in column-major order
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat3 {
+    c0: Vec3,
+    c1: Vec3,
+    c2: Vec3,
+}
+
+impl Mat3 {
+    /// Creates a matrix from its three columns
+    pub fn from_cols(c0: Vec3, c1: Vec3, c2: Vec3) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    /// Computes the outer product `a * b.transpose()`
+    pub fn from_outer_product(a: Vec3, b: Vec3) -> Self {
+        Self {
+            c0: a * b.x,
+            c1: a * b.y,
+            c2: a * b.z,
+        }
+    }
+
+    /// Transforms `v` from world space into the tangent space described by `self`, where `self`
+    /// is a TBN matrix with the tangent, bitangent and normal as its columns.
+    ///
+    /// The TBN basis is assumed to be orthonormal, which lets the inverse be computed as the
+    /// transpose.
+    pub fn world_to_tangent(&self, v: Vec3) -> Vec3 {
+        self.transpose() * v
+    }
+
+    /// Transforms `v` from the tangent space described by the TBN matrix `self` into world space
+    pub fn tangent_to_world(&self, v: Vec3) -> Vec3 {
+        *self * v
+    }
+}
+
+impl Transpose for Mat3 {
+    fn transpose(self) -> Self {
+        Self {
+            c0: Vec3::new(self.c0.x, self.c1.x, self.c2.x),
+            c1: Vec3::new(self.c0.y, self.c1.y, self.c2.y),
+            c2: Vec3::new(self.c0.z, self.c1.z, self.c2.z),
+        }
+    }
+}
+
+impl std::ops::Add for Mat3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            c0: self.c0 + rhs.c0,
+            c1: self.c1 + rhs.c1,
+            c2: self.c2 + rhs.c2,
+        }
+    }
+}
+
+impl std::ops::Mul<f32> for Mat3 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+            c2: self.c2 * rhs,
+        }
+    }
+}
+
+impl num::Zero for Mat3 {
+    fn zero() -> Self {
+        Self {
+            c0: num::zero(),
+            c1: num::zero(),
+            c2: num::zero(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero() && self.c2.is_zero()
+    }
+}
+
+/// Computes the covariance matrix of a point cloud.
+///
+/// The points are centered on their [`centroid`](crate::vec::centroid) and the sum of outer
+/// products is divided by N (the population covariance), not N - 1. An empty slice gives the zero
+/// matrix.
+pub fn covariance_matrix(points: &[Vec3]) -> Mat3 {
+    if points.is_empty() {
+        return num::zero();
+    }
+
+    let center = crate::vec::centroid(points);
+    let sum = points.iter().fold(num::zero(), |acc: Mat3, &p| {
+        let d = p - center;
+        acc + Mat3::from_outer_product(d, d)
+    });
+
+    sum * (1.0 / points.len() as f32)
+}
+
+impl std::ops::Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        self.c0 * rhs.x + self.c1 * rhs.y + self.c2 * rhs.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_applies_rhs_first() {
+        let translation = Mat4 {
+            c0: Vec4::new(1.0, 0.0, 0.0, 0.0),
+            c1: Vec4::new(0.0, 1.0, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, 1.0, 0.0),
+            c3: Vec4::new(1.0, 2.0, 3.0, 1.0),
+        };
+        let scale = Mat4 {
+            c0: Vec4::new(2.0, 0.0, 0.0, 0.0),
+            c1: Vec4::new(0.0, 2.0, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, 2.0, 0.0),
+            c3: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        };
+
+        // Scaling first leaves the translation alone
+        let m = translation * scale;
+        assert_eq!(m.c0, Vec4::new(2.0, 0.0, 0.0, 0.0));
+        assert_eq!(m.c3, Vec4::new(1.0, 2.0, 3.0, 1.0));
+
+        // Translating first means the translation gets scaled too
+        let m = scale * translation;
+        assert_eq!(m.c3, Vec4::new(2.0, 4.0, 6.0, 1.0));
+    }
+
+    #[test]
+    fn translate_composes_on_the_right() {
+        let m = translate(&num::one(), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(m.c0, Vec4::new(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(m.c1, Vec4::new(0.0, 1.0, 0.0, 0.0));
+        assert_eq!(m.c2, Vec4::new(0.0, 0.0, 1.0, 0.0));
+        assert_eq!(m.c3, Vec4::new(1.0, 2.0, 3.0, 1.0));
+
+        let scale = Mat4 {
+            c0: Vec4::new(2.0, 0.0, 0.0, 0.0),
+            c1: Vec4::new(0.0, 2.0, 0.0, 0.0),
+            c2: Vec4::new(0.0, 0.0, 2.0, 0.0),
+            c3: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        };
+        let m = translate(&scale, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(m.c0, scale.c0);
+        assert_eq!(m.c3, Vec4::new(2.0, 4.0, 6.0, 1.0));
+    }
+
+    fn assert_mat_near(a: Mat4, b: Mat4, eps: f32) {
+        for (ca, cb) in [(a.c0, b.c0), (a.c1, b.c1), (a.c2, b.c2), (a.c3, b.c3)] {
+            assert!(ca.max_abs_diff(cb) <= eps, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn interpolate_transform_slerps_rotation() {
+        let a: Mat4 = num::one();
+        let b = Mat4::from_scale_rotation_translation(
+            num::one(),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2),
+            Vec3::new(2.0, 0.0, 0.0),
+        );
+
+        let halfway = Mat4::from_scale_rotation_translation(
+            num::one(),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_4),
+            Vec3::new(1.0, 0.0, 0.0),
+        );
+        let m = interpolate_transform(&a, &b, 0.5);
+        assert_mat_near(m, halfway, 1e-6);
+
+        // An element-wise blend shrinks the basis vectors instead
+        let blended = a.c0.lerp(b.c0, 0.5);
+        assert!((xyz(m.c0).mag() - 1.0).abs() < 1e-6);
+        assert!((xyz(blended).mag() - 1.0).abs() > 0.2);
+    }
+
+    #[test]
+    fn transform_look_inverts_look_to() {
+        let (eye, dir, up) = (
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(-1.0, 0.5, -2.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+
+        let world = transform_look(eye, dir, up);
+        let view = look_to(eye, dir, up);
+        assert_mat_near(world.inverse().expect("rigid transform"), view, 1e-5);
+        assert_mat_near(world * view, num::one(), 1e-5);
+    }
+
+    #[test]
+    fn extract_near_far_recovers_planes() {
+        let (near, far) = perspective(1.0, 1.5, 0.1, 100.0)
+            .extract_near_far()
+            .expect("perspective projection");
+        assert!((near - 0.1).abs() < 1e-5);
+        assert!((far - 100.0).abs() < 1e-2);
+
+        assert_eq!(
+            ortho(-1.0, 1.0, -1.0, 1.0, 0.1, 100.0).extract_near_far(),
+            None
+        );
+    }
+
+    #[test]
+    fn world_units_per_pixel_scales_with_depth() {
+        let proj = perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        assert!((world_units_per_pixel(&proj, 5.0, 100.0) - 0.1).abs() < 1e-6);
+        assert!((world_units_per_pixel(&proj, 10.0, 100.0) - 0.2).abs() < 1e-6);
+
+        let proj = ortho(-2.0, 2.0, -2.0, 2.0, 0.1, 100.0);
+        assert!((world_units_per_pixel(&proj, 5.0, 100.0) - 0.04).abs() < 1e-6);
+        assert!((world_units_per_pixel(&proj, 10.0, 100.0) - 0.04).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cols_array_2d_round_trip() {
+        let cols = [
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ];
+
+        let m = Mat4::from_cols_array_2d(cols);
+        assert_eq!(m.to_cols_array_2d(), cols);
+
+        // The outer index selects a column, not a row
+        assert_eq!(m.c1, Vec4::new(5.0, 6.0, 7.0, 8.0));
+        assert_ne!(m, m.transpose());
+        assert_eq!(m.transpose().c1, Vec4::new(2.0, 6.0, 10.0, 14.0));
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let m = Mat4::from_cols_array_2d([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+
+        let t = m.transpose().to_cols_array_2d();
+        let m = m.to_cols_array_2d();
+        for (i, col) in t.iter().enumerate() {
+            for (j, e) in col.iter().enumerate() {
+                assert_eq!(*e, m[j][i]);
+            }
+        }
+    }
+
+    #[test]
+    fn from_euler_angles_applies_roll_pitch_yaw() {
+        let (pitch, yaw, roll) = (0.3_f32, -1.1_f32, 0.7_f32);
+        let (sx, cx) = pitch.sin_cos();
+        let (sy, cy) = yaw.sin_cos();
+        let (sz, cz) = roll.sin_cos();
+
+        let rx = Mat4::from_cols_array_2d([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cx, sx, 0.0],
+            [0.0, -sx, cx, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let ry = Mat4::from_cols_array_2d([
+            [cy, 0.0, -sy, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [sy, 0.0, cy, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let rz = Mat4::from_cols_array_2d([
+            [cz, sz, 0.0, 0.0],
+            [-sz, cz, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        assert_mat_near(from_euler_angles(pitch, yaw, roll), ry * rx * rz, 1e-6);
+    }
+
+    #[test]
+    fn tangent_space_round_trip() {
+        let (s, c) = 0.6_f32.sin_cos();
+        let tbn = Mat3::from_cols(
+            Vec3::new(c, s, 0.0),
+            Vec3::new(-s, c, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        );
+
+        let v = Vec3::new(0.3, -1.2, 2.5);
+        let local = tbn.world_to_tangent(v);
+        assert!((local.x - (v.x * c + v.y * s)).abs() < 1e-6);
+        assert!(tbn.tangent_to_world(local).max_abs_diff(v) < 1e-6);
+    }
+
+    #[test]
+    fn cholesky_factors_spd_matrices() {
+        let b = Mat4::from_cols_array_2d([
+            [2.0, 0.5, -1.0, 0.0],
+            [1.0, 3.0, 0.0, 0.5],
+            [0.0, -0.5, 1.5, 1.0],
+            [0.5, 0.0, 1.0, 2.0],
+        ]);
+        let a = b * b.transpose() + num::one();
+
+        let l = a.cholesky().expect("positive-definite");
+        let cols = l.to_cols_array_2d();
+        for (j, col) in cols.iter().enumerate() {
+            assert!(col[j] > 0.0);
+            for e in &col[..j] {
+                assert_eq!(*e, 0.0);
+            }
+        }
+        assert_mat_near(l * l.transpose(), a, 1e-5);
+
+        let indefinite = Mat4::from_cols_array_2d([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, -1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(indefinite.cholesky(), None);
+    }
+
+    #[test]
+    fn identity_basis_directions() {
+        let m: Mat4 = num::one();
+        assert_eq!(m.right(), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(m.up(), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(m.forward(), Vec3::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn from_affine2d_stays_in_plane() {
+        let m = Mat4::from_affine2d(
+            Vec2::new(2.0, 3.0),
+            std::f32::consts::FRAC_PI_2,
+            Vec2::new(1.0, -1.0),
+        );
+
+        let p = m * Vec4::new(1.0, 1.0, 0.0, 1.0);
+        assert!(p.max_abs_diff(Vec4::new(-2.0, 1.0, 0.0, 1.0)) < 1e-6);
+        assert_eq!((m * Vec4::new(1.0, 1.0, 5.0, 1.0)).z, 5.0);
+    }
+
+    #[test]
+    fn preserves_orientation_detects_mirroring() {
+        let rotation = rotate(&num::one(), 1.2, Vec3::new(1.0, 1.0, 0.0));
+        assert!(rotation.preserves_orientation());
+
+        let mirror = Mat4::from_scale_rotation_translation(
+            Vec3::new(-1.0, 1.0, 1.0),
+            Quat::new(0.0, 0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, 0.0),
+        );
+        assert!(!mirror.preserves_orientation());
+        assert!(!(rotation * mirror).preserves_orientation());
+    }
+
+    #[test]
+    fn inverse_ortho_matches_inverse() {
+        let proj = ortho(-4.0, 2.0, -1.0, 3.0, 0.5, 50.0);
+        assert_mat_near(
+            proj.inverse_ortho(),
+            proj.inverse().expect("invertible"),
+            1e-5,
+        );
+        assert_mat_near(proj * proj.inverse_ortho(), num::one(), 1e-5);
+    }
+
+    #[test]
+    fn extract_fov_y_recovers_fov() {
+        let fov = perspective(1.2, 1.6, 0.1, 100.0)
+            .extract_fov_y()
+            .expect("perspective projection");
+        assert!((fov - 1.2).abs() < 1e-6);
+        assert_eq!(ortho(-1.0, 1.0, -1.0, 1.0, 0.1, 10.0).extract_fov_y(), None);
+    }
+
+    #[test]
+    fn rotate_around_pivot_fixes_pivot() {
+        let pivot = Vec3::new(1.0, 2.0, -3.0);
+        let m = rotate_around_pivot(pivot, Vec3::new(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2);
+
+        let p = m * Vec4::new(pivot.x, pivot.y, pivot.z, 1.0);
+        assert!(p.max_abs_diff(Vec4::new(1.0, 2.0, -3.0, 1.0)) < 1e-6);
+
+        // A point one unit along +X from the pivot swings round to +Y
+        let p = m * Vec4::new(2.0, 2.0, -3.0, 1.0);
+        assert!(p.max_abs_diff(Vec4::new(1.0, 3.0, -3.0, 1.0)) < 1e-6);
+    }
+
+    #[test]
+    fn for_gl_uniform_layouts() {
+        let m = Mat4::from_cols_array_2d([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+
+        let column_major = m.for_gl_uniform(false);
+        assert_eq!(column_major, *m.to_cols_array_2d().as_flattened());
+        assert_eq!(column_major[12..15], [13.0, 14.0, 15.0]);
+
+        let row_major = m.for_gl_uniform(true);
+        assert_eq!(row_major[..4], [1.0, 5.0, 9.0, 13.0]);
+        assert_eq!(row_major[3], column_major[12]);
+    }
+
+    #[test]
+    fn blend_transforms_matches_interpolate() {
+        let a = Mat4::from_scale_rotation_translation(
+            Vec3::new(1.0, 2.0, 1.0),
+            Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.4),
+            Vec3::new(1.0, 0.0, -2.0),
+        );
+        let b = Mat4::from_scale_rotation_translation(
+            Vec3::new(3.0, 1.0, 1.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 1.4),
+            Vec3::new(-3.0, 4.0, 0.0),
+        );
+
+        assert_mat_near(
+            blend_transforms(&[a, b], &[0.5, 0.5]),
+            interpolate_transform(&a, &b, 0.5),
+            1e-5,
+        );
+        assert_eq!(blend_transforms(&[], &[]), num::one());
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let m = Mat4::from_cols_array_2d([
+            [1.0, -2.0, 3.5, 0.0],
+            [f32::MAX, 6.0, 7.0, 8.0],
+            [9.0, f32::MIN_POSITIVE, 11.0, 12.0],
+            [13.0, 14.0, -0.0, 1.0],
+        ]);
+
+        let bytes = m.to_bytes();
+        assert_eq!(bytes.len(), 65);
+        assert_eq!(bytes[0], crate::WIRE_FORMAT_VERSION);
+        assert_eq!(Mat4::from_bytes(&bytes), Ok(m));
+
+        assert_eq!(
+            Mat4::from_bytes(&bytes[..64]),
+            Err(crate::FromBytesError::InvalidLength {
+                expected: 65,
+                actual: 64
+            })
+        );
+        let mut bad_version = bytes.clone();
+        bad_version[0] = 0;
+        assert_eq!(
+            Mat4::from_bytes(&bad_version),
+            Err(crate::FromBytesError::UnsupportedVersion(0))
+        );
+    }
+
+    #[test]
+    fn zero_is_additive_identity() {
+        let m = rotate(&num::one(), 0.8, Vec3::new(0.0, 1.0, 1.0));
+        let zero: Mat4 = num::zero();
+
+        assert_eq!(m + zero, m);
+        assert!(num::Zero::is_zero(&zero));
+        assert!(!num::Zero::is_zero(&m));
+    }
+
+    // The relaxed-SIMD multiply-add is only fused on some hosts
+    #[cfg(not(target_feature = "relaxed-simd"))]
+    #[test]
+    fn mul_fuses_ill_conditioned_products() {
+        let (a, b) = (1.0 + 2.0f32.powi(-12), 1.0 + 2.0f32.powi(-11));
+        let lhs = Mat4::from_cols_array_2d([
+            [-1.0, 0.0, 0.0, 0.0],
+            [a, 0.0, 0.0, 0.0],
+            [0.0; 4],
+            [0.0; 4],
+        ]);
+        let rhs = Mat4::from_cols_array_2d([[b, a, 0.0, 0.0], [0.0; 4], [0.0; 4], [0.0; 4]]);
+
+        // -b + a * a, where a * a rounds to b in f32
+        let exact = -f64::from(b) + f64::from(a) * f64::from(a);
+        let unfused = -b + a * a;
+        let fused = (lhs * rhs).c0.x;
+
+        assert_eq!(unfused, 0.0);
+        assert!((f64::from(fused) - exact).abs() < (f64::from(unfused) - exact).abs());
+        assert_eq!(f64::from(fused), exact);
+    }
+
+    #[test]
+    fn combine_cols_matches_scalar_reference() {
+        let v = [0.5, -1.0, 2.0, 1.0];
+        for i in 0..100 {
+            let f = i as f32;
+            let cols = [
+                [f.sin(), f.cos(), 0.5, 1.0],
+                [0.25, (2.0 * f).sin(), f.cos() * 0.5, 0.0],
+                [1.0, 0.0, (3.0 * f).cos(), f.sin() * 0.25],
+                [f * 0.01, -1.0, 0.5, 1.0],
+            ];
+            let res = combine_cols(&cols, v);
+            for (r, res) in res.into_iter().enumerate() {
+                let expected: f32 = (0..4).map(|k| cols[k][r] * v[k]).sum();
+                assert!((res - expected).abs() < 1e-5, "{cols:?} * {v:?}");
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "non-finite")]
+    fn mul_rejects_nan_in_debug() {
+        let mut m: Mat4 = num::one();
+        m.c1.y = f32::NAN;
+        let _ = m * num::one::<Mat4>();
+    }
+
+    #[test]
+    fn covariance_of_axis_aligned_box_is_diagonal() {
+        let center = Vec3::new(5.0, -5.0, 2.0);
+        let points: Vec<_> = (0..8)
+            .map(|i| {
+                let sign = |bit: i32| if i & bit == 0 { -1.0 } else { 1.0 };
+                center + Vec3::new(sign(1), 2.0 * sign(2), 3.0 * sign(4))
+            })
+            .collect();
+
+        let cov = covariance_matrix(&points);
+        assert_eq!(
+            cov,
+            Mat3::from_cols(
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 4.0, 0.0),
+                Vec3::new(0.0, 0.0, 9.0)
+            )
+        );
+    }
+
+    #[test]
+    fn pretty_lays_out_rows() {
+        let m = translate(&num::one(), Vec3::new(1.5, -20.0, 3.0));
+        assert_eq!(
+            m.pretty(1),
+            "[   1.0    0.0    0.0    1.5 ]\n\
+             [   0.0    1.0    0.0  -20.0 ]\n\
+             [   0.0    0.0    1.0    3.0 ]\n\
+             [   0.0    0.0    0.0    1.0 ]"
+        );
+    }
+
+    #[test]
+    fn invert_in_place_matches_inverse() {
+        let m = Mat4::from_scale_rotation_translation(
+            Vec3::new(1.0, 2.0, 0.5),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.6, 0.8), 0.9),
+            Vec3::new(3.0, -1.0, 2.0),
+        );
+
+        let mut inv = m;
+        assert!(inv.invert_in_place());
+        assert_eq!(Some(inv), m.inverse());
+        assert_mat_near(m * inv, num::one(), 1e-5);
+
+        let mut singular = Mat4::from_cols_array_2d([[1.0; 4]; 4]);
+        let before = singular;
+        assert!(!singular.invert_in_place());
+        assert_eq!(singular, before);
+    }
+
+    #[test]
+    fn unproject_infinite_round_trip() {
+        let (fov_y, aspect, near) = (1.0, 1.5, 0.1);
+        let view = look_to(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(0.0, -0.2, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+        let view_proj = perspective_infinite(fov_y, aspect, near) * view;
+
+        for p in [Vec3::new(1.5, 1.0, -2.0), Vec3::new(-40.0, -150.0, -900.0)] {
+            let clip = view_proj * Vec4::new(p.x, p.y, p.z, 1.0);
+            let ndc = xyz(clip) * (1.0 / clip.w);
+            let back = unproject_infinite(ndc, &view, fov_y, aspect, near).expect("in front");
+            assert!(back.max_abs_diff(p) < 1e-3 * p.mag(), "{back:?} != {p:?}");
+        }
+
+        assert_eq!(
+            unproject_infinite(Vec3::new(0.0, 0.0, 1.0), &view, fov_y, aspect, near),
+            None
+        );
+    }
+
+    #[test]
+    fn rank_of_degenerate_matrices() {
+        assert_eq!(num::one::<Mat4>().rank(1e-6), 4);
+        assert_eq!(num::zero::<Mat4>().rank(1e-6), 0);
+
+        let duplicated = Mat4::from_cols_array_2d([
+            [1.0, 2.0, 3.0, 4.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [1.0, 2.0, 3.0, 4.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ]);
+        assert_eq!(duplicated.rank(1e-6), 3);
+    }
+
+    fn project_point(m: &Mat4, p: Vec3) -> Vec3 {
+        let p = *m * Vec4::new(p.x, p.y, p.z, 1.0);
+        xyz(p) * (1.0 / p.w)
+    }
+
+    #[test]
+    fn shadows_land_on_the_plane() {
+        // The plane y = -1
+        let plane = Plane::new(Vec3::new(0.0, 1.0, 0.0), 1.0);
+
+        let m = Mat4::shadow_projection(&plane, Vec3::new(1.0, -1.0, 0.0));
+        let shadow = project_point(&m, Vec3::new(0.0, 2.0, 0.5));
+        assert!(shadow.max_abs_diff(Vec3::new(3.0, -1.0, 0.5)) < 1e-5);
+
+        let m = Mat4::shadow_projection_point(&plane, Vec3::new(0.0, 5.0, 0.0));
+        let shadow = project_point(&m, Vec3::new(1.0, 3.0, 0.0));
+        assert!(shadow.max_abs_diff(Vec3::new(3.0, -1.0, 0.0)) < 1e-5);
+        assert!(plane.signed_distance(shadow).abs() < 1e-5);
+    }
+
+    /// Deterministic pseudo-random matrices with elements in `[-1, 1)`
+    fn random_matrices(n: usize) -> impl Iterator<Item = Mat4> {
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+        };
+        (0..n).map(move |_| Mat4::from_cols_array_2d([[0.0; 4]; 4].map(|c| c.map(|_| next()))))
+    }
+
+    #[test]
+    fn determinant_matches_minors() {
+        for m in random_matrices(1000) {
+            let (s, c) = minors(&m.to_cols_array_2d());
+            let expected = det_from_minors(&s, &c);
+            assert!((m.determinant() - expected).abs() < 1e-5, "{m:?}");
+        }
+
+        assert_eq!(num::one::<Mat4>().determinant(), 1.0);
+        let swapped = Mat4::from_cols_array_2d([
+            [0.0, 1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0],
+            [0.0, 0.0, 0.0, 3.0],
+        ]);
+        assert_eq!(swapped.determinant(), -6.0);
+    }
+
+    #[test]
+    fn ndc_depth_at_near_and_far() {
+        let proj = perspective(1.0, 1.0, 0.5, 50.0);
+        assert!((proj.ndc_depth_at(-0.5) + 1.0).abs() < 1e-6);
+        assert!((proj.ndc_depth_at(-50.0) - 1.0).abs() < 1e-5);
+
+        // Most of the depth range is used up close to the camera
+        assert!(proj.ndc_depth_at(-5.0) > 0.8);
+    }
+
+    #[test]
+    fn rotation_quat_removes_skew_and_scale() {
+        let axis = Vec3::new(1.0, 2.0, 2.0).normalize();
+        let q = Quat::from_axis_angle(axis, 0.8);
+        let mut m = Mat4::from(q);
+
+        // Scale X and shear Y towards it, which leaves the X axis and the XY plane unchanged
+        m.c0 = m.c0 * 2.0;
+        m.c1 = m.c1 + m.c0 * 0.3;
+        m.c2 = m.c2 * 0.5;
+
+        let r = m.rotation_quat();
+        assert!((r.mag() - 1.0).abs() < 1e-5);
+        assert!((r.dot_mul(q).abs() - 1.0).abs() < 1e-5, "{r:?} != {q:?}");
+    }
+
+    #[test]
+    fn inverse_batch_matches_inverse() {
+        let mut mats: Vec<Mat4> = random_matrices(16).collect();
+        mats.push(num::zero());
+
+        let mut out = vec![None; mats.len()];
+        inverse_batch(&mats, &mut out);
+        for (m, inv) in mats.iter().zip(&out) {
+            assert_eq!(*inv, m.inverse());
+        }
+        assert_eq!(out.last(), Some(&None));
+    }
+
+    #[test]
+    fn transform_aabb_matches_corners() {
+        let aabb = Aabb::new(Vec3::new(-1.0, 0.5, -2.0), Vec3::new(2.0, 1.5, 0.25));
+        let corners = (0..8).map(|i| {
+            let pick = |bit: usize, lo: f32, hi: f32| if i & bit == 0 { lo } else { hi };
+            Vec3::new(
+                pick(1, aabb.min.x, aabb.max.x),
+                pick(2, aabb.min.y, aabb.max.y),
+                pick(4, aabb.min.z, aabb.max.z),
+            )
+        });
+
+        for mut m in random_matrices(100) {
+            (m.c0.w, m.c1.w, m.c2.w, m.c3.w) = (0.0, 0.0, 0.0, 1.0);
+
+            let mut points = corners.clone().map(|c| project_point(&m, c));
+            let first = points.next().expect("eight corners");
+            let (min, max) = points.fold((first, first), |(min, max), p| {
+                (
+                    Vec3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                    Vec3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+                )
+            });
+
+            let b = m.transform_aabb(&aabb);
+            assert!(b.min.max_abs_diff(min) < 1e-5, "{b:?} != {min:?}..{max:?}");
+            assert!(b.max.max_abs_diff(max) < 1e-5, "{b:?} != {min:?}..{max:?}");
+        }
+    }
+
+    #[test]
+    fn cube_face_view_looks_along_each_axis() {
+        let eye = Vec3::new(1.0, 2.0, 3.0);
+        let faces = [
+            (
+                CubeFace::PositiveX,
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, -1.0, 0.0),
+            ),
+            (
+                CubeFace::NegativeX,
+                Vec3::new(-1.0, 0.0, 0.0),
+                Vec3::new(0.0, -1.0, 0.0),
+            ),
+            (
+                CubeFace::PositiveY,
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+            ),
+            (
+                CubeFace::NegativeY,
+                Vec3::new(0.0, -1.0, 0.0),
+                Vec3::new(0.0, 0.0, -1.0),
+            ),
+            (
+                CubeFace::PositiveZ,
+                Vec3::new(0.0, 0.0, 1.0),
+                Vec3::new(0.0, -1.0, 0.0),
+            ),
+            (
+                CubeFace::NegativeZ,
+                Vec3::new(0.0, 0.0, -1.0),
+                Vec3::new(0.0, -1.0, 0.0),
+            ),
+        ];
+
+        for (face, dir, up) in faces {
+            let view = cube_face_view(face, eye);
+            let forward = project_point(&view, eye + dir);
+            let above = project_point(&view, eye + up);
+            assert!(project_point(&view, eye).mag() < 1e-6, "{face:?}");
+            assert!(
+                forward.max_abs_diff(Vec3::new(0.0, 0.0, -1.0)) < 1e-6,
+                "{face:?}: {forward:?}"
+            );
+            assert!(
+                above.max_abs_diff(Vec3::new(0.0, 1.0, 0.0)) < 1e-6,
+                "{face:?}: {above:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn screen_center_ray_points_forward() {
+        let eye = Vec3::new(1.0, 2.0, 3.0);
+        let forward = Vec3::new(1.0, -0.5, -2.0).normalize();
+        let view = look_to(eye, forward, Vec3::new(0.0, 1.0, 0.0));
+        let proj = perspective(1.0, 1.5, 0.1, 100.0);
+        let inv = (proj * view).inverse().expect("invertible view-projection");
+
+        let ray = screen_to_ray(Vec2::new(0.0, 0.0), &inv, eye);
+        assert_eq!(ray.origin, eye);
+        assert!(ray.direction.max_abs_diff(forward) < 1e-4, "{ray:?}");
+        assert!(
+            ray.direction
+                .max_abs_diff(transform_look(eye, forward, Vec3::new(0.0, 1.0, 0.0)).forward())
+                < 1e-4
+        );
+    }
+
+    #[test]
+    fn row_vector_product_is_transposed_column_product() {
+        let v = Vec4::new(0.5, -1.0, 2.0, 1.0);
+        for m in random_matrices(100) {
+            let row = v * m;
+            let col = m.transpose() * v;
+            assert!(row.max_abs_diff(col) < 1e-5, "{row:?} != {col:?}");
+        }
+
+        let m = Mat4::from_cols_array_2d([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        assert_eq!(
+            Vec4::new(1.0, 0.0, 0.0, 0.0) * m,
+            Vec4::new(1.0, 5.0, 9.0, 13.0)
+        );
+    }
+
+    #[test]
+    fn d3d_clip_maps_near_and_far_to_zero_and_one() {
+        let (near, far) = (0.5, 50.0);
+        for gl in [
+            perspective(1.0, 1.5, near, far),
+            ortho(-2.0, 3.0, -1.0, 1.0, near, far),
+        ] {
+            let d3d = gl.gl_to_d3d_clip();
+
+            let n = project_point(&d3d, Vec3::new(0.0, 0.0, near));
+            let f = project_point(&d3d, Vec3::new(0.0, 0.0, far));
+            assert!(n.z.abs() < 1e-6, "{n:?}");
+            assert!((f.z - 1.0).abs() < 1e-5, "{f:?}");
+
+            // The same point in left-handed view space lands on the same pixel
+            let p = Vec3::new(0.3, -0.2, 4.0);
+            let a = project_point(&d3d, p);
+            let b = project_point(&gl, Vec3::new(p.x, p.y, -p.z));
+            assert!(
+                (a.x - b.x).abs() < 1e-6 && (a.y - b.y).abs() < 1e-6,
+                "{a:?} != {b:?}"
+            );
+
+            assert_mat_near(d3d.d3d_to_gl_clip(), gl, 1e-6);
+        }
+    }
+
+    #[test]
+    fn to_transform_round_trips_transform_matrix() {
+        let t = Transform::new(
+            Vec3::new(1.0, -2.0, 3.0),
+            Quat::from_axis_angle(Vec3::new(2.0, -1.0, 2.0).normalize(), 1.2),
+            Vec3::new(0.5, 2.0, 3.0),
+        );
+        let back = t.matrix().to_transform();
+
+        assert!(
+            back.translation().max_abs_diff(t.translation()) < 1e-5,
+            "{back:?}"
+        );
+        assert!(back.scale().max_abs_diff(t.scale()) < 1e-5, "{back:?}");
+        assert!(
+            (back.rotation().dot_mul(t.rotation()).abs() - 1.0).abs() < 1e-5,
+            "{back:?}"
+        );
+        assert_mat_near(back.matrix(), t.matrix(), 1e-5);
+    }
+
+    #[test]
+    fn ortho_2d_screen_maps_corners_to_ndc() {
+        let (w, h) = (800.0, 600.0);
+        let proj = ortho_2d_screen(w, h);
+        let corners = [
+            (Vec3::new(0.0, 0.0, 0.0), Vec3::new(-1.0, 1.0, 0.0)),
+            (Vec3::new(w, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0)),
+            (Vec3::new(0.0, h, 0.0), Vec3::new(-1.0, -1.0, 0.0)),
+            (Vec3::new(w, h, 0.0), Vec3::new(1.0, -1.0, 0.0)),
+            (Vec3::new(w * 0.5, h * 0.5, 0.5), Vec3::new(0.0, 0.0, -0.5)),
+        ];
+
+        for (screen, ndc) in corners {
+            let p = project_point(&proj, screen);
+            assert!(p.max_abs_diff(ndc) < 1e-6, "{screen:?} -> {p:?} != {ndc:?}");
+        }
+    }
+
+    #[test]
+    fn exp_inverts_log() {
+        let one = Vec3::new(1.0, 1.0, 1.0);
+        let axis = Vec3::new(1.0, -2.0, 2.0).normalize();
+        for angle in [0.0, 1e-4, 0.5, 2.0, 3.0] {
+            let m = Mat4::from_scale_rotation_translation(
+                one,
+                Quat::from_axis_angle(axis, angle),
+                Vec3::new(1.0, 2.0, -3.0),
+            );
+            assert_mat_near(m.log().exp(), m, 1e-4);
+        }
+    }
+
+    #[test]
+    fn screw_interpolate_midpoint() {
+        let one = Vec3::new(1.0, 1.0, 1.0);
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        let screw = |angle: f32, height: f32| {
+            Mat4::from_scale_rotation_translation(
+                one,
+                Quat::from_axis_angle(z, angle),
+                Vec3::new(0.0, 0.0, height),
+            )
+        };
+
+        // Turning 2 radians while rising 4 is halfway at 1 radian and a height of 2
+        let a = Mat4::from_scale_rotation_translation(
+            one,
+            Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.7),
+            Vec3::new(5.0, -1.0, 2.0),
+        );
+        let b = a * screw(2.0, 4.0);
+        assert_mat_near(screw_interpolate(&a, &b, 0.5), a * screw(1.0, 2.0), 1e-5);
+        assert_mat_near(screw_interpolate(&a, &b, 0.0), a, 1e-5);
+        assert_mat_near(screw_interpolate(&a, &b, 1.0), b, 1e-5);
+    }
+
+    #[test]
+    fn mul_vec4_matches_scalar_lanes() {
+        let vectors = [
+            Vec4::new(0.5, -1.0, 2.0, 1.0),
+            Vec4::new(1e4, 3.0, -1e-3, 0.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        ];
+        for m in random_matrices(100) {
+            for v in vectors {
+                assert_eq!(m * v, mul_vec4_lanes(&m, v), "{m:?} * {v:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn without_scale_keeps_rotation_and_translation() {
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.6);
+        let translation = Vec3::new(4.0, -5.0, 6.0);
+        let m = Mat4::from_scale_rotation_translation(Vec3::new(2.0, 0.5, 3.0), q, translation);
+        let unscaled = m.without_scale();
+
+        for c in [unscaled.c0, unscaled.c1, unscaled.c2] {
+            assert!((xyz(c).mag() - 1.0).abs() < 1e-6, "{unscaled:?}");
+            assert_eq!(c.w, 0.0);
+        }
+        assert_eq!(unscaled.c3, m.c3);
+        assert_mat_near(
+            unscaled,
+            Mat4::from_scale_rotation_translation(Vec3::new(1.0, 1.0, 1.0), q, translation),
+            1e-6,
+        );
+    }
+}
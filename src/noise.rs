@@ -0,0 +1,252 @@
+use crate::vec::Dot;
+use crate::{Vec2, Vec3};
+
+/// Ken Perlin's reference permutation table
+const PERM: [u8; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+fn perm(i: i32) -> i32 {
+    i32::from(PERM[(i & 255) as usize])
+}
+
+/// Reduces the integer lattice coordinate `v` to `[0, 256)`.
+///
+/// The noise repeats every 256 cells anyway, and keeping the coordinates small stops the hash
+/// sums from overflowing for large inputs.
+fn lattice(v: f32) -> i32 {
+    v.rem_euclid(256.0) as i32
+}
+
+/// The quintic smoothstep `6t^5 - 15t^4 + 10t^3`, whose first and second derivatives vanish at
+/// 0 and 1
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn grad_2d(hash: i32, offset: Vec2) -> f32 {
+    let g = match hash & 7 {
+        0 => Vec2::new(1.0, 1.0),
+        1 => Vec2::new(-1.0, 1.0),
+        2 => Vec2::new(1.0, -1.0),
+        3 => Vec2::new(-1.0, -1.0),
+        4 => Vec2::new(1.0, 0.0),
+        5 => Vec2::new(-1.0, 0.0),
+        6 => Vec2::new(0.0, 1.0),
+        _ => Vec2::new(0.0, -1.0),
+    };
+
+    g.dot_mul(offset)
+}
+
+fn grad_3d(hash: i32, offset: Vec3) -> f32 {
+    let g = match hash & 15 {
+        0 | 12 => Vec3::new(1.0, 1.0, 0.0),
+        1 | 14 => Vec3::new(-1.0, 1.0, 0.0),
+        2 => Vec3::new(1.0, -1.0, 0.0),
+        3 => Vec3::new(-1.0, -1.0, 0.0),
+        4 => Vec3::new(1.0, 0.0, 1.0),
+        5 => Vec3::new(-1.0, 0.0, 1.0),
+        6 => Vec3::new(1.0, 0.0, -1.0),
+        7 => Vec3::new(-1.0, 0.0, -1.0),
+        8 => Vec3::new(0.0, 1.0, 1.0),
+        9 | 13 => Vec3::new(0.0, -1.0, 1.0),
+        10 => Vec3::new(0.0, 1.0, -1.0),
+        _ => Vec3::new(0.0, -1.0, -1.0),
+    };
+
+    g.dot_mul(offset)
+}
+
+/// Evaluates 2D Perlin gradient noise at `p`.
+///
+/// The result is roughly in `[-1, 1]`, smooth, and zero at every integer lattice point. Noise is
+/// generated from Ken Perlin's fixed permutation table, so it is deterministic and repeats with a
+/// period of 256 along each axis. There is no seed; offset `p` to get a different pattern.
+pub fn perlin_2d(p: Vec2) -> f32 {
+    let (xf, yf) = (p.x.floor(), p.y.floor());
+    let (xi, yi) = (lattice(xf), lattice(yf));
+    let f = Vec2::new(p.x - xf, p.y - yf);
+    let (u, v) = (fade(f.x), fade(f.y));
+
+    let a = perm(xi) + yi;
+    let b = perm(xi + 1) + yi;
+
+    lerp(
+        lerp(
+            grad_2d(perm(a), f),
+            grad_2d(perm(b), f - Vec2::new(1.0, 0.0)),
+            u,
+        ),
+        lerp(
+            grad_2d(perm(a + 1), f - Vec2::new(0.0, 1.0)),
+            grad_2d(perm(b + 1), f - Vec2::new(1.0, 1.0)),
+            u,
+        ),
+        v,
+    )
+}
+
+/// Evaluates 3D Perlin gradient noise at `p`, using the gradient set from Perlin's "Improving
+/// Noise" paper.
+///
+/// The result is roughly in `[-1, 1]`, smooth, and zero at every integer lattice point. Like
+/// [`perlin_2d`] it is deterministic, unseeded and repeats with a period of 256 along each axis.
+pub fn perlin_3d(p: Vec3) -> f32 {
+    let (xf, yf, zf) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (xi, yi, zi) = (lattice(xf), lattice(yf), lattice(zf));
+    let f = Vec3::new(p.x - xf, p.y - yf, p.z - zf);
+    let (u, v, w) = (fade(f.x), fade(f.y), fade(f.z));
+
+    let a = perm(xi) + yi;
+    let aa = perm(a) + zi;
+    let ab = perm(a + 1) + zi;
+    let b = perm(xi + 1) + yi;
+    let ba = perm(b) + zi;
+    let bb = perm(b + 1) + zi;
+
+    let corner =
+        |hash: i32, dx: f32, dy: f32, dz: f32| grad_3d(perm(hash), f - Vec3::new(dx, dy, dz));
+
+    lerp(
+        lerp(
+            lerp(corner(aa, 0.0, 0.0, 0.0), corner(ba, 1.0, 0.0, 0.0), u),
+            lerp(corner(ab, 0.0, 1.0, 0.0), corner(bb, 1.0, 1.0, 0.0), u),
+            v,
+        ),
+        lerp(
+            lerp(
+                corner(aa + 1, 0.0, 0.0, 1.0),
+                corner(ba + 1, 1.0, 0.0, 1.0),
+                u,
+            ),
+            lerp(
+                corner(ab + 1, 0.0, 1.0, 1.0),
+                corner(bb + 1, 1.0, 1.0, 1.0),
+                u,
+            ),
+            v,
+        ),
+        w,
+    )
+}
+
+/// Chris Wellons' `lowbias32` integer hash, a cheap finalizer with good avalanche behavior
+fn hash_u32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+/// Hashes the bit patterns of `components` into a value in `[0, 1)`
+fn hash_components(components: &[f32]) -> f32 {
+    // Adding 0.0 turns -0.0 into 0.0, so the two zeros hash alike
+    let h = components
+        .iter()
+        .fold(0, |h, &c| hash_u32(h ^ (c + 0.0).to_bits()));
+    (h >> 8) as f32 / (1 << 24) as f32
+}
+
+/// Hashes `p` to a pseudo-random value in `[0, 1)`.
+///
+/// Unlike the `fract(sin(dot(p, k)) * m)` shader trick, this hashes the exact bits of the
+/// components with integer arithmetic only, so the same input gives the same value on every
+/// platform. Nearby inputs give unrelated values; use [`perlin_2d`] for smooth noise.
+pub fn hash_to_unit(p: Vec2) -> f32 {
+    hash_components(&[p.x, p.y])
+}
+
+/// The 3D counterpart of [`hash_to_unit`], with the same determinism guarantees
+pub fn hash_to_unit3(p: Vec3) -> f32 {
+    hash_components(&[p.x, p.y, p.z])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{vec2, vec3};
+
+    #[test]
+    fn perlin_handles_large_inputs() {
+        // 3e9 is a multiple of 256, so it lands on the same lattice cell as 0
+        assert_eq!(perlin_2d(vec2(0.5, 3.0e9)), perlin_2d(vec2(0.5, 0.0)));
+        assert_eq!(perlin_2d(vec2(-3.0e9, 0.25)), perlin_2d(vec2(0.0, 0.25)));
+        assert_eq!(
+            perlin_3d(vec3(0.5, 3.0e9, -3.0e9)),
+            perlin_3d(vec3(0.5, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn perlin_is_deterministic_and_periodic() {
+        let p = vec2(12.3, -4.56);
+        assert_eq!(perlin_2d(p), perlin_2d(p));
+        assert!((perlin_2d(p) - perlin_2d(p + 256.0)).abs() < 1e-4);
+
+        let p = vec3(12.3, -4.56, 7.89);
+        assert_eq!(perlin_3d(p), perlin_3d(p));
+        assert!((perlin_3d(p) - perlin_3d(p + 256.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn perlin_is_continuous_and_bounded() {
+        let step = 1.0e-3;
+        let (mut prev_2d, mut prev_3d) =
+            (perlin_2d(vec2(0.0, 0.3)), perlin_3d(vec3(0.0, 0.3, 0.7)));
+        for i in 1..8000 {
+            let x = i as f32 * step;
+            let (n2, n3) = (perlin_2d(vec2(x, 0.3)), perlin_3d(vec3(x, 0.3, 0.7)));
+
+            assert!((n2 - prev_2d).abs() < 0.01, "2D jump at x = {x}");
+            assert!((n3 - prev_3d).abs() < 0.01, "3D jump at x = {x}");
+            assert!(n2.abs() <= 1.0 && n3.abs() <= 1.0);
+            (prev_2d, prev_3d) = (n2, n3);
+        }
+
+        assert_eq!(perlin_2d(vec2(3.0, -7.0)), 0.0);
+        assert_eq!(perlin_3d(vec3(3.0, -7.0, 11.0)), 0.0);
+    }
+
+    #[test]
+    fn hash_is_deterministic_and_spread_out() {
+        assert_eq!(hash_to_unit(vec2(1.5, -2.0)), hash_to_unit(vec2(1.5, -2.0)));
+        assert_eq!(hash_to_unit(vec2(-0.0, 1.0)), hash_to_unit(vec2(0.0, 1.0)));
+        assert_ne!(hash_to_unit(vec2(1.0, 2.0)), hash_to_unit(vec2(2.0, 1.0)));
+        assert_eq!(
+            hash_to_unit3(vec3(1.0, 2.0, 3.0)),
+            hash_to_unit3(vec3(1.0, 2.0, 3.0))
+        );
+
+        // The values over a grid of cells should fill the ten buckets of [0, 1) about evenly
+        let mut buckets = [0u32; 10];
+        for x in 0..100 {
+            for y in 0..100 {
+                let h = hash_to_unit(vec2(x as f32, y as f32));
+                assert!((0.0..1.0).contains(&h), "{h}");
+                buckets[(h * 10.0) as usize] += 1;
+            }
+        }
+        for count in buckets {
+            assert!((900..1100).contains(&count), "{buckets:?}");
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::vec::{Dot, Magnitude};
+use crate::Vec3;
+
+/// A quaternion of `f32`, used to represent rotations
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quat {
+    /// The x component of the vector part
+    pub x: f32,
+    /// The y component of the vector part
+    pub y: f32,
+    /// The z component of the vector part
+    pub z: f32,
+    /// The scalar part
+    pub w: f32,
+}
+
+impl Quat {
+    /// Constructs a new `Quat`
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Creates a rotation of `angle` radians about the unit vector `axis`
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self {
+            x: axis.x * s,
+            y: axis.y * s,
+            z: axis.z * s,
+            w: c,
+        }
+    }
+
+    /// Creates a rotation from the three (orthonormal) columns of a rotation matrix
+    pub(crate) fn from_basis(x: Vec3, y: Vec3, z: Vec3) -> Self {
+        let trace = x.x + y.y + z.z;
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self {
+                x: (y.z - z.y) / s,
+                y: (z.x - x.z) / s,
+                z: (x.y - y.x) / s,
+                w: 0.25 * s,
+            }
+        } else if x.x > y.y && x.x > z.z {
+            let s = (1.0 + x.x - y.y - z.z).sqrt() * 2.0;
+            Self {
+                x: 0.25 * s,
+                y: (y.x + x.y) / s,
+                z: (z.x + x.z) / s,
+                w: (y.z - z.y) / s,
+            }
+        } else if y.y > z.z {
+            let s = (1.0 + y.y - x.x - z.z).sqrt() * 2.0;
+            Self {
+                x: (y.x + x.y) / s,
+                y: 0.25 * s,
+                z: (z.y + y.z) / s,
+                w: (z.x - x.z) / s,
+            }
+        } else {
+            let s = (1.0 + z.z - x.x - y.y).sqrt() * 2.0;
+            Self {
+                x: (z.x + x.z) / s,
+                y: (z.y + y.z) / s,
+                z: 0.25 * s,
+                w: (x.y - y.x) / s,
+            }
+        }
+    }
+
+    /// Returns the three columns of the rotation matrix corresponding to `self`
+    pub(crate) fn to_basis(self) -> [Vec3; 3] {
+        let Self { x, y, z, w } = self;
+        [
+            Vec3::new(
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y + w * z),
+                2.0 * (x * z - w * y),
+            ),
+            Vec3::new(
+                2.0 * (x * y - w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z + w * x),
+            ),
+            Vec3::new(
+                2.0 * (x * z + w * y),
+                2.0 * (y * z - w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ),
+        ]
+    }
+
+    /// Returns `self` scaled to unit length
+    pub fn normalize(self) -> Self {
+        let inv = 1.0 / self.mag();
+        Self {
+            x: self.x * inv,
+            y: self.y * inv,
+            z: self.z * inv,
+            w: self.w * inv,
+        }
+    }
+
+    /// Spherically interpolates between the unit quaternions `self` and `rhs`,
+    /// always taking the shortest path.
+    pub fn slerp(self, rhs: Self, t: f32) -> Self {
+        let mut cos_theta = self.dot_mul(rhs);
+        let rhs = if cos_theta < 0.0 {
+            cos_theta = -cos_theta;
+            Self::new(-rhs.x, -rhs.y, -rhs.z, -rhs.w)
+        } else {
+            rhs
+        };
+
+        // Nearly parallel quaternions would divide by ~0 below, so fall back to a normalized lerp
+        let (a, b) = if cos_theta > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Self {
+            x: self.x * a + rhs.x * b,
+            y: self.y * a + rhs.y * b,
+            z: self.z * a + rhs.z * b,
+            w: self.w * a + rhs.w * b,
+        }
+        .normalize()
+    }
+}
+
+impl Dot for Quat {
+    type Output = f32;
+
+    fn dot_mul(self, rhs: Self) -> Self::Output {
+        let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
+        let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, rhs.w);
+        let res = std::arch::wasm32::f32x4_mul(s, rhs);
+
+        std::arch::wasm32::f32x4_extract_lane::<0>(res)
+            + std::arch::wasm32::f32x4_extract_lane::<1>(res)
+            + std::arch::wasm32::f32x4_extract_lane::<2>(res)
+            + std::arch::wasm32::f32x4_extract_lane::<3>(res)
+    }
+}
+
+impl Magnitude for Quat {
+    fn mag(&self) -> f32 {
+        self.dot_mul(*self).sqrt()
+    }
+}
+
+impl std::ops::Mul for Quat {
+    type Output = Self;
+
+    /// The Hamilton product, which composes the rotation `rhs` followed by `self`
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        }
+    }
+}
+
+impl num::One for Quat {
+    fn one() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        }
+    }
+}
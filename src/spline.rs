@@ -0,0 +1,119 @@
+use crate::vec::Magnitude;
+use crate::Vec3;
+
+/// Evaluates the uniform Catmull-Rom spline segment between `p1` and `p2` at `t` in `[0, 1]`,
+/// using `p0` and `p3` to determine the tangents
+pub fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+/// A Catmull-Rom spline through a sequence of control points that can be traversed at constant
+/// speed.
+///
+/// The curve passes through every control point, with the first and last points duplicated to
+/// provide tangents at the ends. Arc length is approximated by sampling each segment
+/// [`CatmullRomPath::SAMPLES_PER_SEGMENT`] times and summing the chord lengths, so distances are
+/// slightly underestimated on tightly curved segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRomPath {
+    points: Vec<Vec3>,
+    lengths: Vec<f32>,
+}
+
+impl CatmullRomPath {
+    /// The number of chords each segment is divided into when building the arc-length table
+    pub const SAMPLES_PER_SEGMENT: usize = 16;
+
+    /// Creates a path through `points`, precomputing its arc-length table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than two points are given.
+    pub fn new(points: Vec<Vec3>) -> Self {
+        assert!(points.len() >= 2, "A path needs at least two points");
+
+        let samples = (points.len() - 1) * Self::SAMPLES_PER_SEGMENT;
+        let mut path = Self {
+            points,
+            lengths: Vec::with_capacity(samples + 1),
+        };
+
+        let mut total = 0.0;
+        let mut prev = path.point_at(0.0);
+        path.lengths.push(total);
+        for i in 1..=samples {
+            let p = path.point_at(i as f32 / samples as f32);
+            total += (p - prev).mag();
+            path.lengths.push(total);
+            prev = p;
+        }
+
+        path
+    }
+
+    /// The approximate length of the whole path
+    pub fn total_length(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// Evaluates the path at the parameter `t` in `[0, 1]`, with each segment taking up an equal
+    /// range of `t` regardless of its length
+    pub fn point_at(&self, t: f32) -> Vec3 {
+        let last = self.points.len() - 1;
+        let u = t.clamp(0.0, 1.0) * last as f32;
+        let segment = (u as usize).min(last - 1);
+
+        let p = |i: isize| self.points[i.clamp(0, last as isize) as usize];
+        let i = segment as isize;
+        catmull_rom(p(i - 1), p(i), p(i + 1), p(i + 2), u - segment as f32)
+    }
+
+    /// Evaluates the path at `distance` along its length, clamped to `[0, total_length()]`.
+    ///
+    /// Moving `distance` at a constant rate moves along the curve at (approximately) constant
+    /// speed, unlike [`CatmullRomPath::point_at`].
+    pub fn point_at_distance(&self, distance: f32) -> Vec3 {
+        let d = distance.clamp(0.0, self.total_length());
+        let samples = self.lengths.len() - 1;
+        let i = self.lengths.partition_point(|&l| l < d).clamp(1, samples);
+
+        let (l0, l1) = (self.lengths[i - 1], self.lengths[i]);
+        let frac = if l1 > l0 { (d - l0) / (l1 - l0) } else { 0.0 };
+        self.point_at((i as f32 - 1.0 + frac) / samples as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec3;
+
+    #[test]
+    fn point_at_distance_moves_at_constant_speed() {
+        // The second segment is twice as long as the first
+        let path = CatmullRomPath::new(vec![
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(3.0, 0.0, 0.0),
+        ]);
+        assert!((path.total_length() - 3.0).abs() < 1e-4);
+
+        assert_eq!(path.point_at(0.5), vec3(1.0, 0.0, 0.0));
+        let mid = path.point_at_distance(1.5);
+        assert!(mid.max_abs_diff(vec3(1.5, 0.0, 0.0)) < 0.01, "{mid:?}");
+
+        assert_eq!(path.point_at_distance(-1.0), vec3(0.0, 0.0, 0.0));
+        assert!(
+            path.point_at_distance(10.0)
+                .max_abs_diff(vec3(3.0, 0.0, 0.0))
+                < 1e-5
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::cell::Cell;
+
+use crate::mat::{interpolate_transform, Mat4};
+use crate::quat::Quat;
+use crate::Vec3;
+
+/// A transform made up of a translation, a rotation and a scale, applied as
+/// `translate * rotate * scale`.
+///
+/// The composed matrix is cached: [`Transform::matrix`] only recomputes it if one of the parts
+/// has been changed through a setter since the last call. The cache uses a [`Cell`], so a
+/// `Transform` can be shared between readers on one thread but is not `Sync`.
+#[derive(Debug, Clone)]
+pub struct Transform {
+    translation: Vec3,
+    rotation: Quat,
+    scale: Vec3,
+    matrix: Cell<Option<Mat4>>,
+}
+
+impl Transform {
+    /// Constructs a new `Transform`
+    pub fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+            matrix: Cell::new(None),
+        }
+    }
+
+    /// The translation part
+    pub fn translation(&self) -> Vec3 {
+        self.translation
+    }
+
+    /// The rotation part
+    pub fn rotation(&self) -> Quat {
+        self.rotation
+    }
+
+    /// The scale part
+    pub fn scale(&self) -> Vec3 {
+        self.scale
+    }
+
+    /// Replaces the translation part, invalidating the cached matrix
+    pub fn set_translation(&mut self, translation: Vec3) {
+        self.translation = translation;
+        self.matrix.set(None);
+    }
+
+    /// Replaces the rotation part, invalidating the cached matrix
+    pub fn set_rotation(&mut self, rotation: Quat) {
+        self.rotation = rotation;
+        self.matrix.set(None);
+    }
+
+    /// Replaces the scale part, invalidating the cached matrix
+    pub fn set_scale(&mut self, scale: Vec3) {
+        self.scale = scale;
+        self.matrix.set(None);
+    }
+
+    /// Returns the composed matrix, recomputing it only if a part changed since the last call
+    pub fn matrix(&self) -> Mat4 {
+        match self.matrix.get() {
+            Some(m) => m,
+            None => {
+                let m = Mat4::from_scale_rotation_translation(
+                    self.scale,
+                    self.rotation,
+                    self.translation,
+                );
+                self.matrix.set(Some(m));
+                m
+            }
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::new(num::zero(), num::one(), num::one())
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Self) -> bool {
+        self.translation == other.translation
+            && self.rotation == other.rotation
+            && self.scale == other.scale
+    }
+}
+
+/// An animation track of transforms keyed by time.
+///
+/// Keyframes are kept sorted by time. Between two keyframes the transform is blended with
+/// [`interpolate_transform`]; before the first and after the last keyframe it holds still.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformTrack {
+    keyframes: Vec<(f32, Mat4)>,
+}
+
+impl TransformTrack {
+    /// Constructs a new `TransformTrack` from `(time, transform)` keyframes in any order
+    pub fn new(mut keyframes: Vec<(f32, Mat4)>) -> Self {
+        keyframes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { keyframes }
+    }
+
+    /// The keyframes, sorted by time
+    pub fn keyframes(&self) -> &[(f32, Mat4)] {
+        &self.keyframes
+    }
+
+    /// Samples the track at time `t`, returning the identity if it has no keyframes.
+    ///
+    /// A NaN `t` gives the first keyframe.
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    pub fn sample(&self, t: f32) -> Mat4 {
+        let (Some(first), Some(last)) = (self.keyframes.first(), self.keyframes.last()) else {
+            return num::one();
+        };
+        // Negated so that NaN, which compares false both ways, is caught here
+        if !(t > first.0) {
+            return first.1;
+        }
+        if !(t < last.0) {
+            return last.1;
+        }
+
+        // `t` lies strictly inside the track, so there is a keyframe on either side of it
+        let i = self.keyframes.partition_point(|k| k.0 <= t);
+        let (t0, a) = self.keyframes[i - 1];
+        let (t1, b) = self.keyframes[i];
+        interpolate_transform(&a, &b, (t - t0) / (t1 - t0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_cache_tracks_mutation() {
+        let mut t = Transform::new(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5),
+            Vec3::new(2.0, 2.0, 2.0),
+        );
+
+        let m = t.matrix();
+        assert_eq!(t.matrix.get(), Some(m));
+        assert_eq!(t.matrix(), m);
+
+        t.set_translation(Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(t.matrix.get(), None);
+        let moved = t.matrix();
+        assert_ne!(moved, m);
+        assert_eq!(
+            moved,
+            Mat4::from_scale_rotation_translation(t.scale(), t.rotation(), t.translation())
+        );
+    }
+
+    #[test]
+    fn track_sample_hits_keyframes_and_blends_between() {
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        let one = Vec3::new(1.0, 1.0, 1.0);
+        let key = |angle: f32, x: f32| {
+            Mat4::from_scale_rotation_translation(
+                one,
+                Quat::from_axis_angle(z, angle),
+                Vec3::new(x, 0.0, 0.0),
+            )
+        };
+        let (a, b, c) = (key(0.0, 0.0), key(1.0, 0.0), key(1.0, 4.0));
+        let track = TransformTrack::new(vec![(2.0, c), (0.0, a), (1.0, b)]);
+
+        assert_eq!(track.sample(0.0), a);
+        assert_eq!(track.sample(1.0), b);
+        assert_eq!(track.sample(2.0), c);
+        assert_eq!(track.sample(-1.0), a);
+        assert_eq!(track.sample(3.0), c);
+        assert_eq!(track.sample(f32::NAN), a);
+
+        for (t, expected) in [(0.5, key(0.5, 0.0)), (1.5, key(1.0, 2.0))] {
+            let m = track.sample(t).to_cols_array_2d().concat();
+            let e = expected.to_cols_array_2d().concat();
+            assert!(
+                m.iter().zip(&e).all(|(m, e)| (m - e).abs() < 1e-5),
+                "{m:?} != {e:?}"
+            );
+        }
+
+        assert_eq!(TransformTrack::default().sample(0.0), num::one());
+    }
+}
@@ -69,8 +69,158 @@ macro_rules! impl_vec_mag {
     };
 }
 
+macro_rules! impl_vec_normalize {
+    ($vec:ident) => {
+        impl $vec {
+            /// Returns a vector pointing in the same direction as `self` with unit magnitude
+            pub fn normalize(self) -> Self {
+                self * (1.0 / self.mag())
+            }
+
+            /// Returns the unit vector pointing in the direction of `self` along with the original
+            /// magnitude, computing the square root only once.
+            ///
+            /// Vectors with a magnitude below `f32::EPSILON` have no meaningful direction and
+            /// yield `(zero, 0.0)`.
+            pub fn normalize_and_length(self) -> (Self, f32) {
+                let len = self.mag();
+                if len <= f32::EPSILON {
+                    (::num::zero(), 0.0)
+                } else {
+                    (self * (1.0 / len), len)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_lerp {
+    ($vec:ident) => {
+        impl $vec {
+            /// Linearly interpolates between `self` and `rhs`.
+            ///
+            /// `t` is clamped to `[0, 1]`, so the result always lies between the two endpoints. Use
+            /// [`Self::lerp_unclamped`] to extrapolate.
+            pub fn lerp(self, rhs: Self, t: f32) -> Self {
+                self.lerp_unclamped(rhs, t.clamp(0.0, 1.0))
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_with {
+    ($vec:ident, $($with:ident => $field:ident),+) => {
+        impl $vec {
+            $(
+                #[doc = concat!("Returns a copy of `self` with the ", stringify!($field), " component replaced by `", stringify!($field), "`")]
+                pub fn $with(self, $field: f32) -> Self {
+                    Self { $field, ..self }
+                }
+            )+
+        }
+    };
+}
+
+macro_rules! impl_vec_dot_f64 {
+    ($vec:ident, $($field:ident),+) => {
+        impl $vec {
+            /// Computes the dot product of `self` with `rhs`, accumulating in `f64`.
+            ///
+            /// Slower than [`Dot::dot_mul`], but prefer it when the products are large and nearly
+            /// cancel out, where `f32` accumulation loses most of the significant digits.
+            pub fn dot_f64(self, rhs: Self) -> f64 {
+                0.0 $(+ f64::from(self.$field) * f64::from(rhs.$field))+
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_element_index {
+    ($vec:ident) => {
+        impl $vec {
+            /// Returns the index of the largest component. Ties resolve to the lowest index.
+            pub fn max_element_index(&self) -> usize {
+                let a = self.as_array();
+                let a = a.as_ref();
+                (1..a.len()).fold(0, |best, i| if a[i] > a[best] { i } else { best })
+            }
+
+            /// Returns the index of the smallest component. Ties resolve to the lowest index.
+            pub fn min_element_index(&self) -> usize {
+                let a = self.as_array();
+                let a = a.as_ref();
+                (1..a.len()).fold(0, |best, i| if a[i] < a[best] { i } else { best })
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_abs_diff {
+    ($vec:ident, $($field:ident),+) => {
+        impl $vec {
+            /// Returns the component-wise absolute difference `|self - other|`
+            pub fn abs_diff(self, other: Self) -> Self {
+                Self {
+                    $($field: (self.$field - other.$field).abs()),+
+                }
+            }
+
+            /// Returns the largest component of [`Self::abs_diff`], i.e. the Chebyshev distance
+            pub fn max_abs_diff(self, other: Self) -> f32 {
+                let d = self.abs_diff(other);
+                0.0f32 $(.max(d.$field))+
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_mean {
+    ($vec:ident, $($field:ident),+) => {
+        impl $vec {
+            /// Returns the unweighted average of the components of `self`
+            pub fn mean(&self) -> f32 {
+                let a = [$(self.$field),+];
+                a.iter().sum::<f32>() / a.len() as f32
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_saturate {
+    ($vec:ident, $($field:ident),+) => {
+        impl $vec {
+            /// Clamps each component of `self` to `[0, 1]`.
+            ///
+            /// See [`Self::clamp`] for how NaN components are handled.
+            pub fn saturate(self) -> Self {
+                self.clamp(::num::zero(), Self { $($field: 1.0),+ })
+            }
+        }
+    };
+}
+
+macro_rules! impl_vec_slice {
+    ($vec:ident, $n:literal) => {
+        impl $vec {
+            /// Views the components of `self` as a slice without copying them
+            pub fn as_slice(&self) -> &[f32] {
+                // SAFETY: `$vec` is `#[repr(C)]` and consists solely of `$n` `f32` fields, so it
+                // has the same layout as `[f32; $n]`.
+                unsafe { std::slice::from_raw_parts((self as *const Self).cast::<f32>(), $n) }
+            }
+
+            /// Views the components of `self` as a mutable slice without copying them
+            pub fn as_mut_slice(&mut self) -> &mut [f32] {
+                // SAFETY: See `as_slice`.
+                unsafe { std::slice::from_raw_parts_mut((self as *mut Self).cast::<f32>(), $n) }
+            }
+        }
+    };
+}
+
 /// A two-component vector of `f32`
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
 pub struct Vec2 {
     /// The x component
     pub x: f32,
@@ -82,6 +232,11 @@ impl_vec_new!(Vec2, x, y);
 impl_vec_zero!(Vec2, x, y);
 impl_vec_array!(Vec2, x, y);
 impl_vec_mag!(Vec2, x, y);
+impl_vec_normalize!(Vec2);
+impl_vec_slice!(Vec2, 2);
+impl_vec_lerp!(Vec2);
+impl_vec_mean!(Vec2, x, y);
+impl_vec_saturate!(Vec2, x, y);
 
 impl Vector for Vec2 {}
 
@@ -173,8 +328,192 @@ impl std::ops::Sub for Vec2 {
     }
 }
 
+impl Vec2 {
+    /// Linearly interpolates between `self` and `rhs` without clamping `t`, so values outside
+    /// `[0, 1]` extrapolate beyond the endpoints
+    pub fn lerp_unclamped(self, rhs: Self, t: f32) -> Self {
+        self + (rhs - self) * t
+    }
+
+    /// Clamps each component of `self` to the range given by the matching components of `min`
+    /// and `max`.
+    ///
+    /// A NaN in `self`, `min` or `max` makes that component of the result NaN. If a component of
+    /// `min` exceeds the one in `max`, the result is the `max` component.
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let s = std::arch::wasm32::f32x4(self.x, self.y, 0.0, 0.0);
+            let lo = std::arch::wasm32::f32x4(min.x, min.y, 0.0, 0.0);
+            let hi = std::arch::wasm32::f32x4(max.x, max.y, 0.0, 0.0);
+            let res = std::arch::wasm32::f32x4_min(std::arch::wasm32::f32x4_max(s, lo), hi);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: clamp_lane(self.x, min.x, max.x),
+                y: clamp_lane(self.y, min.y, max.y),
+            }
+        }
+    }
+
+    /// Negates the components of `self` for which `mask` is `true`, leaving the others as is.
+    ///
+    /// Only the sign bit is flipped, so zeros and NaNs change sign too.
+    pub fn negate_where(self, mask: [bool; 2]) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let sign = |m: bool| if m { 0x8000_0000 } else { 0 };
+            let s = std::arch::wasm32::f32x4(self.x, self.y, 0.0, 0.0);
+            let bits = std::arch::wasm32::u32x4(sign(mask[0]), sign(mask[1]), 0, 0);
+            let res = std::arch::wasm32::v128_xor(s, bits);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: negate_lane(self.x, mask[0]),
+                y: negate_lane(self.y, mask[1]),
+            }
+        }
+    }
+
+    /// Decodes a unit vector from its octahedral encoding, as produced by [`Vec3::encode_oct`]
+    pub fn decode_oct(self) -> Vec3 {
+        let z = 1.0 - self.x.abs() - self.y.abs();
+        let t = (-z).max(0.0);
+
+        Vec3::new(
+            self.x - t * sign_not_zero(self.x),
+            self.y - t * sign_not_zero(self.y),
+            z,
+        )
+        .normalize()
+    }
+
+    /// Converts equirectangular (latitude-longitude) texture coordinates to a unit direction, the
+    /// inverse of [`Vec3::dir_to_equirect_uv`]
+    pub fn equirect_uv_to_dir(self) -> Vec3 {
+        let phi = (self.x - 0.5) * std::f32::consts::TAU;
+        let (sin_theta, cos_theta) = (self.y * std::f32::consts::PI).sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        Vec3::new(sin_theta * sin_phi, cos_theta, -sin_theta * cos_phi)
+    }
+}
+
+fn f32_to_f16(v: f32) -> u16 {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+
+    if exp == 0xff {
+        // Infinity stays infinity and NaN stays a (quiet) NaN
+        return sign | 0x7c00 | if man != 0 { 0x200 } else { 0 };
+    }
+
+    let exp = exp - 127 + 15;
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    // Shift the mantissa down to 10 bits (fewer for subnormals) and round to nearest-even. A
+    // carry out of the mantissa correctly bumps the exponent, up to infinity.
+    let (man, shift) = if exp <= 0 {
+        if exp < -10 {
+            return sign;
+        }
+        (man | 0x80_0000, (14 - exp) as u32)
+    } else {
+        (man, 13)
+    };
+    let half = (man >> shift) as u16 | if exp > 0 { (exp as u16) << 10 } else { 0 };
+    let rem = man & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    let round_up = rem > halfway || (rem == halfway && half & 1 == 1);
+
+    sign | (half + u16::from(round_up))
+}
+
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = u32::from(h & 0x8000) << 16;
+    let exp = u32::from((h >> 10) & 0x1f);
+    let man = u32::from(h & 0x3ff);
+
+    match exp {
+        0 => {
+            let v = man as f32 / (1 << 24) as f32;
+            if sign != 0 {
+                -v
+            } else {
+                v
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (man << 13)),
+        _ => f32::from_bits(sign | ((exp + 112) << 23) | (man << 13)),
+    }
+}
+
+/// Clamps a single component with the NaN behavior of the wasm `f32x4_max`/`f32x4_min` pair.
+///
+/// This is the fallback for `clamp` without `simd128`, and the reference its SIMD version is
+/// tested against.
+#[cfg(any(test, not(target_feature = "simd128")))]
+fn clamp_lane(v: f32, lo: f32, hi: f32) -> f32 {
+    if v.is_nan() || lo.is_nan() || hi.is_nan() {
+        f32::NAN
+    } else {
+        v.max(lo).min(hi)
+    }
+}
+
+/// Linearly interpolates a single component, rounding like the wasm `f32x4_sub`, `f32x4_mul`
+/// and `f32x4_add` sequence.
+///
+/// This is the fallback for `lerp_unclamped` without `simd128`, and the reference its SIMD
+/// version is tested against.
+#[cfg(any(test, not(target_feature = "simd128")))]
+fn lerp_lane(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Negates a single component if `negate` is set, flipping only its sign bit like the wasm
+/// `v128_xor` does.
+///
+/// This is the fallback for `negate_where` without `simd128`, and the reference its SIMD version
+/// is tested against.
+#[cfg(any(test, not(target_feature = "simd128")))]
+fn negate_lane(v: f32, negate: bool) -> f32 {
+    if negate {
+        -v
+    } else {
+        v
+    }
+}
+
+fn sign_not_zero(v: f32) -> f32 {
+    if v >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
 /// A three-component vector of `f32`
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
 pub struct Vec3 {
     /// The x (red) component
     pub x: f32,
@@ -188,6 +527,15 @@ impl_vec_new!(Vec3, x, y, z);
 impl_vec_zero!(Vec3, x, y, z);
 impl_vec_array!(Vec3, x, y, z);
 impl_vec_mag!(Vec3, x, y, z);
+impl_vec_normalize!(Vec3);
+impl_vec_slice!(Vec3, 3);
+impl_vec_lerp!(Vec3);
+impl_vec_mean!(Vec3, x, y, z);
+impl_vec_saturate!(Vec3, x, y, z);
+impl_vec_dot_f64!(Vec3, x, y, z);
+impl_vec_element_index!(Vec3);
+impl_vec_abs_diff!(Vec3, x, y, z);
+impl_vec_with!(Vec3, with_x => x, with_y => y, with_z => z);
 
 impl Vector for Vec3 {}
 
@@ -307,8 +655,305 @@ impl num::One for Vec3 {
     }
 }
 
+impl Vec3 {
+    /// Converts each component to IEEE 754 half precision, rounding to nearest-even.
+    ///
+    /// Half floats keep only 11 significant bits (about 3 decimal digits) and range up to 65504;
+    /// larger magnitudes become infinity and magnitudes below about 6e-8 flush to zero.
+    pub fn to_f16_bits(&self) -> [u16; 3] {
+        [self.x, self.y, self.z].map(f32_to_f16)
+    }
+
+    /// Creates a vector from IEEE 754 half-precision components, as produced by
+    /// [`Vec3::to_f16_bits`]. The conversion is exact.
+    pub fn from_f16_bits(bits: [u16; 3]) -> Vec3 {
+        let [x, y, z] = bits.map(f16_to_f32);
+        Vec3 { x, y, z }
+    }
+
+    /// Formats `self` compactly as `(x, y, z)` with `precision` decimal places, for debugging
+    pub fn pretty(&self, precision: usize) -> String {
+        format!(
+            "({:.precision$}, {:.precision$}, {:.precision$})",
+            self.x, self.y, self.z
+        )
+    }
+
+    /// Maps the unit direction `self` to texture coordinates in an equirectangular
+    /// (latitude-longitude) environment map.
+    ///
+    /// `u` runs once around the horizon with -Z at its center (`u = 0.5`), and `v` runs from
+    /// +Y at `v = 0` to -Y at `v = 1`. The seam where `u` wraps from 1 back to 0 lies along +Z, and
+    /// at the poles every `u` maps to the same direction, so `u` is arbitrary there.
+    pub fn dir_to_equirect_uv(self) -> Vec2 {
+        Vec2::new(
+            self.x.atan2(-self.z) / std::f32::consts::TAU + 0.5,
+            self.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI,
+        )
+    }
+
+    /// Encodes the unit vector `self` into the `[-1, 1]` square using an octahedral mapping.
+    ///
+    /// The sphere is projected onto an octahedron whose lower half is folded over the upper one,
+    /// giving a two-component encoding with error spread fairly evenly over the sphere. At full
+    /// `f32` precision the round trip through [`Vec2::decode_oct`] is nearly lossless, so the
+    /// angular error is dominated by any quantization applied when storing the result.
+    pub fn encode_oct(self) -> Vec2 {
+        let n = self * (1.0 / (self.x.abs() + self.y.abs() + self.z.abs()));
+        if n.z >= 0.0 {
+            Vec2::new(n.x, n.y)
+        } else {
+            Vec2::new(
+                (1.0 - n.y.abs()) * sign_not_zero(n.x),
+                (1.0 - n.x.abs()) * sign_not_zero(n.y),
+            )
+        }
+    }
+
+    /// Formats `self` as an `#rrggbb` color string.
+    ///
+    /// Each component is clamped to `[0, 1]` and rounded to the nearest of 256 levels.
+    pub fn to_hex(&self) -> String {
+        let [r, g, b] = [self.x, self.y, self.z].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
+    /// Parses a color from either the long `#rrggbb` or the short `#rgb` hex form, where each
+    /// short-form digit is repeated (so `#f80` is `#ff8800`). Digits are case-insensitive.
+    pub fn from_hex(s: &str) -> Result<Vec3, ParseHexError> {
+        let digits = s.strip_prefix('#').ok_or(ParseHexError::MissingHash)?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseHexError::InvalidDigit);
+        }
+
+        let channel = |s: &str| u8::from_str_radix(s, 16).map_err(|_| ParseHexError::InvalidDigit);
+        let [r, g, b] = match digits.len() {
+            3 => [
+                channel(&digits[0..1])? * 17,
+                channel(&digits[1..2])? * 17,
+                channel(&digits[2..3])? * 17,
+            ],
+            6 => [
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            ],
+            _ => return Err(ParseHexError::InvalidLength),
+        };
+
+        Ok(Vec3::new(
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+        ))
+    }
+
+    /// Adds `other` to `self`, clamping each component of the result to `[0, 1]`.
+    pub fn saturating_add(self, other: Self) -> Self {
+        let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, 0.0);
+        let rhs = std::arch::wasm32::f32x4(other.x, other.y, other.z, 0.0);
+        let res = std::arch::wasm32::f32x4_add(s, rhs);
+        let res = std::arch::wasm32::f32x4_max(res, std::arch::wasm32::f32x4_splat(0.0));
+        let res = std::arch::wasm32::f32x4_min(res, std::arch::wasm32::f32x4_splat(1.0));
+
+        Self {
+            x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+            y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+            z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+        }
+    }
+
+    /// Computes the relative luminance of the linear RGB color `self`.
+    ///
+    /// Uses the Rec. 709 weights, so green counts the most and blue the least.
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.x + 0.7152 * self.y + 0.0722 * self.z
+    }
+
+    /// Converts the HSV color `self` to RGB.
+    ///
+    /// `x` is the hue in degrees, wrapped into `[0, 360)`, so 0 is red, 120 green and 240 blue.
+    /// `y` is the saturation and `z` the value, both in `[0, 1]`. With zero saturation the hue is
+    /// irrelevant and the result is the grey `(v, v, v)`.
+    pub fn hsv_to_rgb(self) -> Vec3 {
+        let (h, s, v) = (self.x.rem_euclid(360.0) / 60.0, self.y, self.z);
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let m = v - c;
+
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        Vec3::new(r + m, g + m, b + m)
+    }
+
+    /// Converts the RGB color `self` to HSV, the inverse of [`Vec3::hsv_to_rgb`].
+    ///
+    /// The hue is returned in degrees in `[0, 360)`. Greys, including black, have no defined hue
+    /// and get a hue and saturation of 0.
+    pub fn rgb_to_hsv(self) -> Vec3 {
+        let (r, g, b) = (self.x, self.y, self.z);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+
+        Vec3::new(h, s, max)
+    }
+
+    /// Projects `self` onto the plane through the origin with normal `normal`.
+    ///
+    /// `normal` must be unit length; otherwise the result is not on the plane.
+    pub fn project_on_plane(self, normal: Vec3) -> Vec3 {
+        self - normal * self.dot_mul(normal)
+    }
+
+    /// Returns the unit axis (±X, ±Y or ±Z) most closely aligned with `self`.
+    ///
+    /// The axis is chosen by the component with the largest magnitude and keeps its sign. Ties go
+    /// to the earlier axis, X before Y before Z, and the zero vector gives +X.
+    pub fn nearest_axis(self) -> Vec3 {
+        let (ax, ay, az) = (self.x.abs(), self.y.abs(), self.z.abs());
+        let sign = |v: f32| if v < 0.0 { -1.0 } else { 1.0 };
+
+        if ax >= ay && ax >= az {
+            Vec3::new(sign(self.x), 0.0, 0.0)
+        } else if ay >= az {
+            Vec3::new(0.0, sign(self.y), 0.0)
+        } else {
+            Vec3::new(0.0, 0.0, sign(self.z))
+        }
+    }
+
+    /// Linearly interpolates between `self` and `rhs` without clamping `t`, so values outside
+    /// `[0, 1]` extrapolate beyond the endpoints
+    pub fn lerp_unclamped(self, rhs: Self, t: f32) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, 0.0);
+            let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, 0.0);
+            let diff = std::arch::wasm32::f32x4_sub(rhs, s);
+            let res = std::arch::wasm32::f32x4_add(
+                s,
+                std::arch::wasm32::f32x4_mul(diff, std::arch::wasm32::f32x4_splat(t)),
+            );
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: lerp_lane(self.x, rhs.x, t),
+                y: lerp_lane(self.y, rhs.y, t),
+                z: lerp_lane(self.z, rhs.z, t),
+            }
+        }
+    }
+
+    /// Clamps each component of `self` to the range given by the matching components of `min`
+    /// and `max`.
+    ///
+    /// A NaN in `self`, `min` or `max` makes that component of the result NaN. If a component of
+    /// `min` exceeds the one in `max`, the result is the `max` component.
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, 0.0);
+            let lo = std::arch::wasm32::f32x4(min.x, min.y, min.z, 0.0);
+            let hi = std::arch::wasm32::f32x4(max.x, max.y, max.z, 0.0);
+            let res = std::arch::wasm32::f32x4_min(std::arch::wasm32::f32x4_max(s, lo), hi);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: clamp_lane(self.x, min.x, max.x),
+                y: clamp_lane(self.y, min.y, max.y),
+                z: clamp_lane(self.z, min.z, max.z),
+            }
+        }
+    }
+
+    /// Negates the components of `self` for which `mask` is `true`, leaving the others as is.
+    ///
+    /// Only the sign bit is flipped, so zeros and NaNs change sign too.
+    pub fn negate_where(self, mask: [bool; 3]) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let sign = |m: bool| if m { 0x8000_0000 } else { 0 };
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, 0.0);
+            let bits = std::arch::wasm32::u32x4(sign(mask[0]), sign(mask[1]), sign(mask[2]), 0);
+            let res = std::arch::wasm32::v128_xor(s, bits);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: negate_lane(self.x, mask[0]),
+                y: negate_lane(self.y, mask[1]),
+                z: negate_lane(self.z, mask[2]),
+            }
+        }
+    }
+}
+
+/// The error returned by [`Vec3::from_hex`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseHexError {
+    /// The string does not start with `#`
+    MissingHash,
+    /// The string does not contain exactly 3 or 6 digits
+    InvalidLength,
+    /// The string contains a character that is not a hex digit
+    InvalidDigit,
+}
+
+impl std::fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHash => write!(f, "hex color must start with '#'"),
+            Self::InvalidLength => write!(f, "hex color must have 3 or 6 digits"),
+            Self::InvalidDigit => write!(f, "hex color contains an invalid digit"),
+        }
+    }
+}
+
+impl std::error::Error for ParseHexError {}
+
 /// A four-component vector of `f32`
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
 pub struct Vec4 {
     /// The x (red) component
     pub x: f32,
@@ -324,6 +969,15 @@ impl_vec_new!(Vec4, x, y, z, w);
 impl_vec_zero!(Vec4, x, y, z, w);
 impl_vec_array!(Vec4, x, y, z, w);
 impl_vec_mag!(Vec4, x, y, z, w);
+impl_vec_normalize!(Vec4);
+impl_vec_slice!(Vec4, 4);
+impl_vec_lerp!(Vec4);
+impl_vec_mean!(Vec4, x, y, z, w);
+impl_vec_saturate!(Vec4, x, y, z, w);
+impl_vec_dot_f64!(Vec4, x, y, z, w);
+impl_vec_element_index!(Vec4);
+impl_vec_abs_diff!(Vec4, x, y, z, w);
+impl_vec_with!(Vec4, with_x => x, with_y => y, with_z => z, with_w => w);
 
 impl Vector for Vec4 {}
 
@@ -398,7 +1052,7 @@ impl std::ops::Add for Vec4 {
 
     fn add(self, rhs: Self) -> Self::Output {
         let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
-        let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, self.w);
+        let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, rhs.w);
         let res = std::arch::wasm32::f32x4_add(s, rhs);
 
         Self {
@@ -415,7 +1069,7 @@ impl std::ops::Sub for Vec4 {
 
     fn sub(self, rhs: Self) -> Self::Output {
         let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
-        let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, self.w);
+        let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, rhs.w);
         let res = std::arch::wasm32::f32x4_sub(s, rhs);
 
         Self {
@@ -427,6 +1081,169 @@ impl std::ops::Sub for Vec4 {
     }
 }
 
+impl Vec4 {
+    /// Encodes `self` for binary transport.
+    ///
+    /// The format is the [`crate::WIRE_FORMAT_VERSION`] byte followed by the x, y, z and w
+    /// components as little-endian `f32`s, 17 bytes in total.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::encode_f32s(&[self.x, self.y, self.z, self.w])
+    }
+
+    /// Decodes a vector encoded by [`Vec4::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::FromBytesError> {
+        let [x, y, z, w] = crate::decode_f32s(bytes)?;
+        Ok(Self { x, y, z, w })
+    }
+
+    /// Adds `other` to `self`, clamping each component of the result to `[0, 1]`.
+    pub fn saturating_add(self, other: Self) -> Self {
+        let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
+        let rhs = std::arch::wasm32::f32x4(other.x, other.y, other.z, other.w);
+        let res = std::arch::wasm32::f32x4_add(s, rhs);
+        let res = std::arch::wasm32::f32x4_max(res, std::arch::wasm32::f32x4_splat(0.0));
+        let res = std::arch::wasm32::f32x4_min(res, std::arch::wasm32::f32x4_splat(1.0));
+
+        Self {
+            x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+            y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+            z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+            w: std::arch::wasm32::f32x4_extract_lane::<3>(res),
+        }
+    }
+
+    /// Linearly interpolates between `self` and `rhs` without clamping `t`, so values outside
+    /// `[0, 1]` extrapolate beyond the endpoints
+    pub fn lerp_unclamped(self, rhs: Self, t: f32) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
+            let rhs = std::arch::wasm32::f32x4(rhs.x, rhs.y, rhs.z, rhs.w);
+            let diff = std::arch::wasm32::f32x4_sub(rhs, s);
+            let res = std::arch::wasm32::f32x4_add(
+                s,
+                std::arch::wasm32::f32x4_mul(diff, std::arch::wasm32::f32x4_splat(t)),
+            );
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+                w: std::arch::wasm32::f32x4_extract_lane::<3>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: lerp_lane(self.x, rhs.x, t),
+                y: lerp_lane(self.y, rhs.y, t),
+                z: lerp_lane(self.z, rhs.z, t),
+                w: lerp_lane(self.w, rhs.w, t),
+            }
+        }
+    }
+
+    /// Computes `self * a + b` per lane with fused multiply-adds, which round once instead of
+    /// twice and so lose less precision.
+    ///
+    /// With `-C target-feature=+relaxed-simd` the relaxed-SIMD multiply-add is used instead, and
+    /// whether it is actually fused depends on the host CPU.
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        #[cfg(target_feature = "relaxed-simd")]
+        {
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
+            let a = std::arch::wasm32::f32x4(a.x, a.y, a.z, a.w);
+            let b = std::arch::wasm32::f32x4(b.x, b.y, b.z, b.w);
+            let res = std::arch::wasm32::f32x4_relaxed_madd(s, a, b);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+                w: std::arch::wasm32::f32x4_extract_lane::<3>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "relaxed-simd"))]
+        {
+            Self {
+                x: self.x.mul_add(a.x, b.x),
+                y: self.y.mul_add(a.y, b.y),
+                z: self.z.mul_add(a.z, b.z),
+                w: self.w.mul_add(a.w, b.w),
+            }
+        }
+    }
+
+    /// Clamps each component of `self` to the range given by the matching components of `min`
+    /// and `max`.
+    ///
+    /// A NaN in `self`, `min` or `max` makes that component of the result NaN. If a component of
+    /// `min` exceeds the one in `max`, the result is the `max` component.
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
+            let lo = std::arch::wasm32::f32x4(min.x, min.y, min.z, min.w);
+            let hi = std::arch::wasm32::f32x4(max.x, max.y, max.z, max.w);
+            let res = std::arch::wasm32::f32x4_min(std::arch::wasm32::f32x4_max(s, lo), hi);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+                w: std::arch::wasm32::f32x4_extract_lane::<3>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: clamp_lane(self.x, min.x, max.x),
+                y: clamp_lane(self.y, min.y, max.y),
+                z: clamp_lane(self.z, min.z, max.z),
+                w: clamp_lane(self.w, min.w, max.w),
+            }
+        }
+    }
+
+    /// Negates the components of `self` for which `mask` is `true`, leaving the others as is.
+    ///
+    /// Only the sign bit is flipped, so zeros and NaNs change sign too.
+    pub fn negate_where(self, mask: [bool; 4]) -> Self {
+        #[cfg(target_feature = "simd128")]
+        {
+            let sign = |m: bool| if m { 0x8000_0000 } else { 0 };
+            let s = std::arch::wasm32::f32x4(self.x, self.y, self.z, self.w);
+            let bits = std::arch::wasm32::u32x4(
+                sign(mask[0]),
+                sign(mask[1]),
+                sign(mask[2]),
+                sign(mask[3]),
+            );
+            let res = std::arch::wasm32::v128_xor(s, bits);
+
+            Self {
+                x: std::arch::wasm32::f32x4_extract_lane::<0>(res),
+                y: std::arch::wasm32::f32x4_extract_lane::<1>(res),
+                z: std::arch::wasm32::f32x4_extract_lane::<2>(res),
+                w: std::arch::wasm32::f32x4_extract_lane::<3>(res),
+            }
+        }
+
+        #[cfg(not(target_feature = "simd128"))]
+        {
+            Self {
+                x: negate_lane(self.x, mask[0]),
+                y: negate_lane(self.y, mask[1]),
+                z: negate_lane(self.z, mask[2]),
+                w: negate_lane(self.w, mask[3]),
+            }
+        }
+    }
+}
+
 /// Computes the distance between two vectors using Pythagoras's theorem.
 pub fn distance<V>(v1: &V, v2: &V) -> f32
 where
@@ -436,6 +1253,176 @@ where
     (*v2 - *v1).mag()
 }
 
+/// Computes `out[i] = a[i].dot_mul(b[i])` for every element.
+///
+/// Groups of four vectors are transposed into lanes so that four dot products are computed per
+/// SIMD instruction; any remainder is handled one at a time.
+///
+/// # Panics
+///
+/// Panics if `a`, `b` and `out` don't all have the same length.
+pub fn dot_batch(a: &[Vec3], b: &[Vec3], out: &mut [f32]) {
+    assert!(
+        a.len() == b.len() && a.len() == out.len(),
+        "dot_batch inputs and output must have the same length"
+    );
+
+    let mut a_chunks = a.chunks_exact(4);
+    let mut b_chunks = b.chunks_exact(4);
+    let mut out_chunks = out.chunks_exact_mut(4);
+    for ((a, b), out) in (&mut a_chunks).zip(&mut b_chunks).zip(&mut out_chunks) {
+        let ax = std::arch::wasm32::f32x4(a[0].x, a[1].x, a[2].x, a[3].x);
+        let ay = std::arch::wasm32::f32x4(a[0].y, a[1].y, a[2].y, a[3].y);
+        let az = std::arch::wasm32::f32x4(a[0].z, a[1].z, a[2].z, a[3].z);
+        let bx = std::arch::wasm32::f32x4(b[0].x, b[1].x, b[2].x, b[3].x);
+        let by = std::arch::wasm32::f32x4(b[0].y, b[1].y, b[2].y, b[3].y);
+        let bz = std::arch::wasm32::f32x4(b[0].z, b[1].z, b[2].z, b[3].z);
+
+        let res = std::arch::wasm32::f32x4_add(
+            std::arch::wasm32::f32x4_add(
+                std::arch::wasm32::f32x4_mul(ax, bx),
+                std::arch::wasm32::f32x4_mul(ay, by),
+            ),
+            std::arch::wasm32::f32x4_mul(az, bz),
+        );
+
+        out[0] = std::arch::wasm32::f32x4_extract_lane::<0>(res);
+        out[1] = std::arch::wasm32::f32x4_extract_lane::<1>(res);
+        out[2] = std::arch::wasm32::f32x4_extract_lane::<2>(res);
+        out[3] = std::arch::wasm32::f32x4_extract_lane::<3>(res);
+    }
+
+    for ((a, b), out) in a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .zip(out_chunks.into_remainder())
+    {
+        *out = a.dot_mul(*b);
+    }
+}
+
+/// Computes the centroid (mean position) of `points`, or the origin if there are none
+pub fn centroid(points: &[Vec3]) -> Vec3 {
+    if points.is_empty() {
+        return num::zero();
+    }
+
+    let sum = points.iter().fold(num::zero(), |acc: Vec3, &p| acc + p);
+    sum * (1.0 / points.len() as f32)
+}
+
+/// Bilinearly interpolates four corner values of a grid cell.
+///
+/// The corners are named `v{u}{v}` after their position in the cell: `v00` at `uv = (0, 0)`, `v10`
+/// at `(1, 0)`, `v01` at `(0, 1)` and `v11` at `(1, 1)`. `uv` is the fractional position inside
+/// the cell and is not clamped.
+pub fn bilerp(v00: f32, v10: f32, v01: f32, v11: f32, uv: Vec2) -> f32 {
+    let bottom = v00 + (v10 - v00) * uv.x;
+    let top = v01 + (v11 - v01) * uv.x;
+    bottom + (top - bottom) * uv.y
+}
+
+/// Bilinearly interpolates four vector-valued corners of a grid cell, with the same corner
+/// ordering as [`bilerp`]
+pub fn bilerp_vec3(v00: Vec3, v10: Vec3, v01: Vec3, v11: Vec3, uv: Vec2) -> Vec3 {
+    let bottom = v00.lerp_unclamped(v10, uv.x);
+    let top = v01.lerp_unclamped(v11, uv.x);
+    bottom.lerp_unclamped(top, uv.y)
+}
+
+/// Interpolates a vertex attribute across a triangle, returning
+/// `a * bary.x + b * bary.y + c * bary.z`.
+///
+/// Works for any of the crate's vector types, as well as colors and plain `f32`s. The weights are
+/// used as given, so they should sum to one for a point inside the triangle.
+pub fn interpolate_barycentric<T>(bary: Vec3, a: T, b: T, c: T) -> T
+where
+    T: std::ops::Mul<f32, Output = T> + std::ops::Add<Output = T>,
+{
+    a * bary.x + b * bary.y + c * bary.z
+}
+
+/// Computes the velocity after bouncing off a surface with unit normal `normal`.
+///
+/// The velocity is split into the part along the normal and the part tangent to the surface. The
+/// normal part is reflected and scaled by `restitution` (0 for a dead stop, 1 for a perfectly
+/// elastic bounce), while the tangential part is scaled by `1 - friction` (0 for a frictionless
+/// surface, 1 to stop all sliding). Both coefficients are expected to be in `[0, 1]`.
+pub fn bounce(velocity: Vec3, normal: Vec3, restitution: f32, friction: f32) -> Vec3 {
+    let normal_part = normal * velocity.dot_mul(normal);
+    let tangent_part = velocity - normal_part;
+
+    tangent_part * (1.0 - friction) - normal_part * restitution
+}
+
+/// Refracts the unit vector `incident` through a surface with unit normal `normal`, where `eta`
+/// is the ratio of the refractive indices (incoming over outgoing).
+///
+/// Returns `None` on total internal reflection, so callers such as ray tracers can spawn a
+/// reflection ray instead.
+pub fn refract_checked(incident: Vec3, normal: Vec3, eta: f32) -> Option<Vec3> {
+    let cos_i = normal.dot_mul(incident);
+    let k = 1.0 - eta * eta * (1.0 - cos_i * cos_i);
+
+    (k >= 0.0).then(|| incident * eta - normal * (eta * cos_i + k.sqrt()))
+}
+
+/// Refracts `incident` like GLSL's `refract`, returning the zero vector on total internal
+/// reflection.
+///
+/// Equivalent to [`refract_checked`] with `None` mapped to zero, which makes the two cases hard
+/// to tell apart; prefer [`refract_checked`] outside shader ports.
+pub fn refract(incident: Vec3, normal: Vec3, eta: f32) -> Vec3 {
+    refract_checked(incident, normal, eta).unwrap_or(num::zero())
+}
+
+/// Maps two uniform samples in `[0, 1)` to a unit direction in the hemisphere around the unit
+/// vector `normal`.
+///
+/// Directions are distributed with probability density `cos(θ) / π`, where `θ` is the angle to
+/// `normal`, which matches the Lambertian term and so is the usual importance-sampling choice
+/// for diffuse surfaces. Samples are generated by projecting a uniformly sampled disk up onto the
+/// hemisphere (Malley's method).
+pub fn cosine_sample_hemisphere(normal: Vec3, u1: f32, u2: f32) -> Vec3 {
+    let r = u1.sqrt();
+    let (sin_phi, cos_phi) = (2.0 * std::f32::consts::PI * u2).sin_cos();
+
+    // Branchless orthonormal basis around `normal` (Duff et al. 2017)
+    let sign = 1.0f32.copysign(normal.z);
+    let a = -1.0 / (sign + normal.z);
+    let b = normal.x * normal.y * a;
+    let tangent = Vec3::new(
+        1.0 + sign * normal.x * normal.x * a,
+        sign * b,
+        -sign * normal.x,
+    );
+    let bitangent = Vec3::new(b, sign + normal.y * normal.y * a, -normal.y);
+
+    tangent * (r * cos_phi) + bitangent * (r * sin_phi) + normal * (1.0 - u1).max(0.0).sqrt()
+}
+
+/// Evaluates the nine real spherical harmonics basis functions of bands 0 to 2 for the unit
+/// direction `dir`.
+///
+/// The functions are orthonormal over the sphere (the convention used by most irradiance
+/// environment map papers) and ordered by `l * (l + 1) + m`, i.e. `Y00, Y1-1, Y10, Y11, Y2-2,
+/// Y2-1, Y20, Y21, Y22`.
+pub fn sh_basis_l2(dir: Vec3) -> [f32; 9] {
+    let Vec3 { x, y, z } = dir;
+    [
+        0.282_094_8,
+        0.488_602_5 * y,
+        0.488_602_5 * z,
+        0.488_602_5 * x,
+        1.092_548_4 * x * y,
+        1.092_548_4 * y * z,
+        0.315_391_6 * (3.0 * z * z - 1.0),
+        1.092_548_4 * x * z,
+        0.546_274_2 * (x * x - y * y),
+    ]
+}
+
 /// Creates a new two-component vector
 pub fn vec2(x: f32, y: f32) -> Vec2 {
     Vec2 { x, y }
@@ -450,3 +1437,525 @@ pub fn vec3(x: f32, y: f32, z: f32) -> Vec3 {
 pub fn vec4(x: f32, y: f32, z: f32, w: f32) -> Vec4 {
     Vec4 { x, y, z, w }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec4_add_sub_use_rhs_w() {
+        let a = vec4(1.0, 2.0, 3.0, 4.0);
+        let b = vec4(0.5, 1.0, 1.5, 2.0);
+
+        assert_eq!(a + b, vec4(1.5, 3.0, 4.5, 6.0));
+        assert_eq!(a - b, vec4(0.5, 1.0, 1.5, 2.0));
+    }
+
+    #[test]
+    fn sh_basis_l2_for_z_axis() {
+        let pi = std::f32::consts::PI;
+        let expected = [
+            0.5 / pi.sqrt(),
+            0.0,
+            (3.0 / (4.0 * pi)).sqrt(),
+            0.0,
+            0.0,
+            0.0,
+            (5.0 / pi).sqrt() / 2.0,
+            0.0,
+            0.0,
+        ];
+
+        let basis = sh_basis_l2(vec3(0.0, 0.0, 1.0));
+        for (b, e) in basis.iter().zip(expected) {
+            assert!((b - e).abs() < 1e-6, "{basis:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn as_mut_slice_writes_through() {
+        let mut v = vec3(1.0, 2.0, 3.0);
+        v.as_mut_slice()[0] = 7.0;
+        assert_eq!(v.x, 7.0);
+        assert_eq!(v.as_slice(), &[7.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn saturating_add_clamps_to_unit_range() {
+        assert_eq!(
+            vec3(0.5, 0.25, -0.5).saturating_add(vec3(0.75, 0.25, 0.25)),
+            vec3(1.0, 0.5, 0.0)
+        );
+        assert_eq!(
+            vec4(0.5, 0.25, -0.5, 0.9).saturating_add(vec4(0.75, 0.25, 0.25, 0.5)),
+            vec4(1.0, 0.5, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let orange = Vec3::from_hex("#ff8000").expect("valid hex color");
+        assert_eq!(orange, vec3(1.0, 128.0 / 255.0, 0.0));
+        assert_eq!(orange.to_hex(), "#ff8000");
+        assert_eq!(Vec3::from_hex("#F80"), Vec3::from_hex("#ff8800"));
+
+        assert_eq!(Vec3::from_hex("ff8000"), Err(ParseHexError::MissingHash));
+        assert_eq!(Vec3::from_hex("#ff80"), Err(ParseHexError::InvalidLength));
+        assert_eq!(Vec3::from_hex("#ff80zz"), Err(ParseHexError::InvalidDigit));
+    }
+
+    #[test]
+    fn lerp_clamps_t() {
+        let (a, b) = (vec2(0.0, 2.0), vec2(4.0, -2.0));
+        assert_eq!(a.lerp(b, -0.5), a);
+        assert_eq!(a.lerp(b, 1.5), b);
+        assert_eq!(a.lerp_unclamped(b, -0.5), vec2(-2.0, 4.0));
+        assert_eq!(a.lerp_unclamped(b, 1.5), vec2(6.0, -4.0));
+
+        let (a, b) = (vec3(0.0, 2.0, 1.0), vec3(4.0, -2.0, 1.0));
+        assert_eq!(a.lerp(b, -0.5), a);
+        assert_eq!(a.lerp(b, 1.5), b);
+    }
+
+    #[test]
+    fn with_replaces_one_component() {
+        assert_eq!(vec3(1.0, 2.0, 3.0).with_y(9.0), vec3(1.0, 9.0, 3.0));
+        assert_eq!(
+            vec4(1.0, 2.0, 3.0, 4.0).with_w(0.0),
+            vec4(1.0, 2.0, 3.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn dot_f64_survives_cancellation() {
+        // 1e8 + 1 isn't representable as an f32, so the 1 is lost when accumulating in f32
+        let a = vec3(1.0e8, 1.0, -1.0e8);
+        let b = vec3(1.0, 1.0, 1.0);
+        assert_eq!(a.dot_f64(b), 1.0);
+        assert_ne!(a.dot_mul(b), 1.0);
+
+        let a = vec4(3.0e7, 0.5, -3.0e7, 0.25);
+        let b = vec4(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(a.dot_f64(b), 0.75);
+        assert_ne!(a.dot_mul(b), 0.75);
+    }
+
+    #[test]
+    fn element_index_distinct_and_tied() {
+        let v = vec3(2.0, -1.0, 5.0);
+        assert_eq!(v.max_element_index(), 2);
+        assert_eq!(v.min_element_index(), 1);
+
+        let v = vec4(3.0, 0.0, 3.0, 0.0);
+        assert_eq!(v.max_element_index(), 0);
+        assert_eq!(v.min_element_index(), 1);
+    }
+
+    /// Points spread evenly over the unit sphere along a Fibonacci spiral
+    fn sphere_points(n: usize) -> impl Iterator<Item = Vec3> {
+        let golden = std::f32::consts::PI * (3.0 - 5.0_f32.sqrt());
+        (0..n).map(move |i| {
+            let y = 1.0 - 2.0 * (i as f32 + 0.5) / n as f32;
+            let r = (1.0 - y * y).sqrt();
+            let (s, c) = (golden * i as f32).sin_cos();
+            vec3(r * c, y, r * s)
+        })
+    }
+
+    #[test]
+    fn oct_round_trip_error() {
+        let mut max_err = 0.0f32;
+        for dir in sphere_points(1000) {
+            let decoded = dir.encode_oct().decode_oct();
+            // |a x b| is the sine of the angle between the unit vectors a and b
+            max_err = max_err.max((dir * decoded).mag().asin());
+            assert!(decoded.dot_mul(dir) > 0.0);
+        }
+        assert!(max_err < 1e-5, "max angular error {max_err}");
+    }
+
+    #[test]
+    fn normalize_and_length_matches_mag() {
+        let v = vec3(3.0, -4.0, 12.0);
+        let (dir, len) = v.normalize_and_length();
+        assert_eq!(len, v.mag());
+        assert!((dir.mag() - 1.0).abs() < 1e-6);
+        assert!((dir * len).max_abs_diff(v) < 1e-5);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let v = vec4(1.0, -2.5, 1.0e-3, f32::MAX);
+        let bytes = v.to_bytes();
+        assert_eq!(bytes.len(), 17);
+        assert_eq!(Vec4::from_bytes(&bytes), Ok(v));
+
+        let mut oversized = bytes.clone();
+        oversized.push(0);
+        assert_eq!(
+            Vec4::from_bytes(&oversized),
+            Err(crate::FromBytesError::InvalidLength {
+                expected: 17,
+                actual: 18
+            })
+        );
+        assert!(Vec4::from_bytes(&bytes[..16]).is_err());
+    }
+
+    #[test]
+    fn bounce_head_on_and_glancing() {
+        let normal = vec3(0.0, 1.0, 0.0);
+        assert_eq!(
+            bounce(vec3(0.0, -4.0, 0.0), normal, 0.5, 0.25),
+            vec3(0.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            bounce(vec3(4.0, -2.0, 0.0), normal, 0.5, 0.25),
+            vec3(3.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn abs_diff_per_component() {
+        let (a, b) = (vec3(1.0, -2.0, 3.0), vec3(4.0, -1.0, 3.0));
+        assert_eq!(a.abs_diff(b), vec3(3.0, 1.0, 0.0));
+        assert_eq!(a.max_abs_diff(b), 3.0);
+        assert_eq!(b.max_abs_diff(a), 3.0);
+
+        let (a, b) = (vec4(0.0, 0.0, 0.0, -5.0), vec4(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(a.abs_diff(b), vec4(1.0, 0.0, 0.0, 5.0));
+        assert_eq!(a.max_abs_diff(b), 5.0);
+    }
+
+    #[test]
+    fn refraction_follows_snell() {
+        let normal = vec3(0.0, 1.0, 0.0);
+        let (sin_i, cos_i) = 0.7_f32.sin_cos();
+        let incident = vec3(sin_i, -cos_i, 0.0);
+        let eta = 1.0 / 1.33;
+
+        let refracted =
+            refract_checked(incident, normal, eta).expect("no total internal reflection");
+        assert!((refracted.mag() - 1.0).abs() < 1e-6);
+        assert!((refracted.x - eta * sin_i).abs() < 1e-6);
+        assert!(refracted.y < 0.0);
+
+        // Going the other way at a steep angle is reflected entirely
+        let (sin_i, cos_i) = 1.0_f32.sin_cos();
+        let incident = vec3(sin_i, -cos_i, 0.0);
+        assert_eq!(refract_checked(incident, normal, 1.33), None);
+        assert_eq!(refract(incident, normal, 1.33), vec3(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn cosine_samples_stay_in_hemisphere() {
+        for normal in [
+            vec3(0.0, 0.0, 1.0),
+            vec3(0.0, 0.0, -1.0),
+            vec3(1.0, 2.0, -3.0).normalize(),
+        ] {
+            for i in 0..16 {
+                for j in 0..16 {
+                    let (u1, u2) = (i as f32 / 16.0, j as f32 / 16.0);
+                    let dir = cosine_sample_hemisphere(normal, u1, u2);
+                    assert!((dir.mag() - 1.0).abs() < 1e-5);
+                    assert!(dir.dot_mul(normal) > 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dot_batch_matches_dot_mul() {
+        // 11 isn't a multiple of 4, so the remainder path runs too
+        let a: Vec<_> = (0..11)
+            .map(|i| vec3(i as f32, 0.5 * i as f32, -2.0))
+            .collect();
+        let b: Vec<_> = (0..11)
+            .map(|i| vec3(1.0, -(i as f32), 0.25 * i as f32))
+            .collect();
+
+        let mut out = vec![0.0; 11];
+        dot_batch(&a, &b, &mut out);
+        for ((a, b), out) in a.iter().zip(&b).zip(&out) {
+            assert_eq!(*out, a.dot_mul(*b));
+        }
+    }
+
+    #[test]
+    fn f16_round_trip_and_overflow() {
+        let v = vec3(1.0, -0.333_251_95, 65504.0);
+        assert_eq!(v.to_f16_bits(), [0x3c00, 0xb555, 0x7bff]);
+        assert_eq!(Vec3::from_f16_bits(v.to_f16_bits()), v);
+
+        // One ulp of rounding at most
+        let v = vec3(0.1, 2.71, -1000.7);
+        let back = Vec3::from_f16_bits(v.to_f16_bits());
+        assert!(back.abs_diff(v).x <= 0.1 / 1024.0);
+        assert!(back.abs_diff(v).y <= 2.0 / 1024.0);
+        assert!(back.abs_diff(v).z <= 512.0 / 1024.0);
+
+        let [big, small, neg] = vec3(1.0e5, 1.0e-9, -1.0e6).to_f16_bits();
+        assert_eq!(f16_to_f32(big), f32::INFINITY);
+        assert_eq!(f16_to_f32(small), 0.0);
+        assert_eq!(f16_to_f32(neg), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn bilerp_corners_and_center() {
+        let (v00, v10, v01, v11) = (1.0, 2.0, 3.0, 6.0);
+        assert_eq!(bilerp(v00, v10, v01, v11, vec2(0.0, 0.0)), v00);
+        assert_eq!(bilerp(v00, v10, v01, v11, vec2(1.0, 0.0)), v10);
+        assert_eq!(bilerp(v00, v10, v01, v11, vec2(0.0, 1.0)), v01);
+        assert_eq!(bilerp(v00, v10, v01, v11, vec2(1.0, 1.0)), v11);
+        assert_eq!(bilerp(v00, v10, v01, v11, vec2(0.5, 0.5)), 3.0);
+
+        let corners = [
+            vec3(0.0, 0.0, 1.0),
+            vec3(2.0, 0.0, 1.0),
+            vec3(0.0, 2.0, 1.0),
+            vec3(2.0, 2.0, 5.0),
+        ];
+        let [c00, c10, c01, c11] = corners;
+        assert_eq!(bilerp_vec3(c00, c10, c01, c11, vec2(1.0, 0.0)), c10);
+        assert_eq!(
+            bilerp_vec3(c00, c10, c01, c11, vec2(0.5, 0.5)),
+            vec3(1.0, 1.0, 2.0)
+        );
+    }
+
+    /// Whether `a` and `b` are equal, counting any two NaNs as equal
+    fn same(a: f32, b: f32) -> bool {
+        a == b || (a.is_nan() && b.is_nan())
+    }
+
+    #[test]
+    fn clamp_matches_scalar_lanes() {
+        let values = [-2.0, -0.0, 0.5, 1.0, 3.0, f32::INFINITY, f32::NAN];
+        for &v in &values {
+            for &lo in &values {
+                for &hi in &values {
+                    let expected = clamp_lane(v, lo, hi);
+                    let res = vec4(v, v, v, v).clamp(vec4(lo, lo, lo, lo), vec4(hi, hi, hi, hi));
+                    assert!(same(res.x, expected) && same(res.w, expected));
+                    assert!(same(
+                        vec3(v, v, v).clamp(vec3(lo, lo, lo), vec3(hi, hi, hi)).z,
+                        expected
+                    ));
+                    assert!(same(
+                        vec2(v, v).clamp(vec2(lo, lo), vec2(hi, hi)).y,
+                        expected
+                    ));
+                }
+            }
+        }
+
+        assert!(vec3(f32::NAN, 0.5, 2.0)
+            .clamp(num::zero(), num::one())
+            .x
+            .is_nan());
+        assert_eq!(clamp_lane(0.5, 2.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn nearest_axis_per_axis_and_ties() {
+        assert_eq!(vec3(3.0, -1.0, 2.0).nearest_axis(), vec3(1.0, 0.0, 0.0));
+        assert_eq!(vec3(0.5, -4.0, 2.0).nearest_axis(), vec3(0.0, -1.0, 0.0));
+        assert_eq!(vec3(0.5, 1.0, -2.0).nearest_axis(), vec3(0.0, 0.0, -1.0));
+
+        assert_eq!(vec3(-2.0, 2.0, 2.0).nearest_axis(), vec3(-1.0, 0.0, 0.0));
+        assert_eq!(vec3(0.0, 2.0, -2.0).nearest_axis(), vec3(0.0, 1.0, 0.0));
+        assert_eq!(vec3(0.0, 0.0, 0.0).nearest_axis(), vec3(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn mean_averages_components() {
+        assert_eq!(vec2(1.0, 2.0).mean(), 1.5);
+        assert_eq!(vec3(1.0, 2.0, 6.0).mean(), 3.0);
+        assert_eq!(vec4(1.0, 2.0, 3.0, -2.0).mean(), 1.0);
+    }
+
+    #[test]
+    fn luminance_uses_rec709_weights() {
+        assert_eq!(vec3(0.0, 0.0, 0.0).luminance(), 0.0);
+        assert!((vec3(1.0, 1.0, 1.0).luminance() - 1.0).abs() < 1e-6);
+        assert_eq!(vec3(1.0, 0.0, 0.0).luminance(), 0.2126);
+        assert_eq!(vec3(0.0, 1.0, 0.0).luminance(), 0.7152);
+        assert_eq!(vec3(0.0, 0.0, 1.0).luminance(), 0.0722);
+    }
+
+    #[test]
+    fn hsv_round_trips_primaries_and_greys() {
+        let colors = [
+            (vec3(0.0, 1.0, 1.0), vec3(1.0, 0.0, 0.0)),
+            (vec3(120.0, 1.0, 1.0), vec3(0.0, 1.0, 0.0)),
+            (vec3(240.0, 1.0, 1.0), vec3(0.0, 0.0, 1.0)),
+            (vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 0.0)),
+            (vec3(0.0, 0.0, 0.5), vec3(0.5, 0.5, 0.5)),
+            (vec3(0.0, 0.0, 1.0), vec3(1.0, 1.0, 1.0)),
+        ];
+        for (hsv, rgb) in colors {
+            assert_eq!(hsv.hsv_to_rgb(), rgb);
+            assert_eq!(rgb.rgb_to_hsv(), hsv);
+        }
+
+        // The hue is irrelevant for greys and wraps around outside [0, 360)
+        assert_eq!(vec3(200.0, 0.0, 0.5).hsv_to_rgb(), vec3(0.5, 0.5, 0.5));
+        assert_eq!(vec3(480.0, 1.0, 1.0).hsv_to_rgb(), vec3(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn equirect_round_trip() {
+        for d in sphere_points(1000) {
+            let back = d.dir_to_equirect_uv().equirect_uv_to_dir();
+            assert!(back.max_abs_diff(d) < 1e-5, "{d:?} != {back:?}");
+        }
+
+        let center = vec3(0.0, 0.0, -1.0).dir_to_equirect_uv();
+        assert!((center - vec2(0.5, 0.5)).mag() < 1e-6, "{center:?}");
+        assert!(vec3(0.0, 1.0, 0.0).dir_to_equirect_uv().y.abs() < 1e-6);
+        assert!((vec3(0.0, -1.0, 0.0).dir_to_equirect_uv().y - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mul_add_matches_unfused() {
+        let unfused = |s: Vec4, a: Vec4, b: Vec4| {
+            vec4(
+                s.x * a.x + b.x,
+                s.y * a.y + b.y,
+                s.z * a.z + b.z,
+                s.w * a.w + b.w,
+            )
+        };
+        let (s, a, b) = (
+            vec4(1.5, -2.0, 0.25, 3.0),
+            vec4(2.0, 0.5, -4.0, 1.0 / 3.0),
+            vec4(-1.0, 1.0, 0.5, 2.0),
+        );
+        assert!(s.mul_add(a, b).max_abs_diff(unfused(s, a, b)) < 1e-6);
+
+        // (1 + 2^-12)^2 = 1 + 2^-11 + 2^-24, and the last term is lost when the product is rounded
+        // on its own
+        let x = 1.0 + 2.0f32.powi(-12);
+        let v = vec4(x, x, x, x);
+        let c = -(1.0 + 2.0f32.powi(-11));
+        let c = vec4(c, c, c, c);
+        assert_eq!(unfused(v, v, c), vec4(0.0, 0.0, 0.0, 0.0));
+
+        #[cfg(not(target_feature = "relaxed-simd"))]
+        {
+            let e = 2.0f32.powi(-24);
+            assert_eq!(v.mul_add(v, c), vec4(e, e, e, e));
+        }
+    }
+
+    #[test]
+    fn interpolate_barycentric_centroid_and_colors() {
+        let (a, b, c) = (
+            vec3(0.0, 0.0, 0.0),
+            vec3(3.0, 0.0, 0.0),
+            vec3(0.0, 6.0, 3.0),
+        );
+        let third = 1.0 / 3.0;
+        let centroid = interpolate_barycentric(vec3(third, third, third), a, b, c);
+        assert!(
+            centroid.max_abs_diff(vec3(1.0, 2.0, 1.0)) < 1e-6,
+            "{centroid:?}"
+        );
+
+        let red = crate::color::Color3::new(1.0, 0.0, 0.0);
+        let green = crate::color::Color3::new(0.0, 1.0, 0.0);
+        let blue = crate::color::Color3::new(0.0, 0.0, 1.0);
+        assert_eq!(
+            interpolate_barycentric(vec3(0.0, 1.0, 0.0), red, green, blue),
+            green
+        );
+        assert_eq!(
+            interpolate_barycentric(vec3(0.5, 0.25, 0.25), red, green, blue),
+            crate::color::Color3::new(0.5, 0.25, 0.25)
+        );
+
+        assert_eq!(
+            interpolate_barycentric(vec3(0.5, 0.5, 0.0), 2.0, 4.0, 8.0),
+            3.0
+        );
+    }
+
+    #[test]
+    fn project_on_xz_plane_drops_y() {
+        let up = vec3(0.0, 1.0, 0.0);
+        assert_eq!(
+            vec3(1.0, 2.0, -3.0).project_on_plane(up),
+            vec3(1.0, 0.0, -3.0)
+        );
+        assert_eq!(
+            vec3(0.0, -5.0, 0.0).project_on_plane(up),
+            vec3(0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            vec3(4.0, 0.0, 1.0).project_on_plane(up),
+            vec3(4.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn lerp_unclamped_matches_scalar_lanes() {
+        let (a, b) = (vec4(1.0, -2.0, 0.3, 1e6), vec4(-3.0, 5.0, 0.7, -1e-3));
+        for t in [-1.5, -0.0, 0.0, 0.1, 1.0 / 3.0, 0.5, 0.9, 1.0, 2.5] {
+            let expected = vec4(
+                lerp_lane(a.x, b.x, t),
+                lerp_lane(a.y, b.y, t),
+                lerp_lane(a.z, b.z, t),
+                lerp_lane(a.w, b.w, t),
+            );
+            assert_eq!(a.lerp_unclamped(b, t), expected, "t = {t}");
+
+            let (a3, b3) = (vec3(a.x, a.y, a.w), vec3(b.x, b.y, b.w));
+            assert_eq!(
+                a3.lerp_unclamped(b3, t),
+                vec3(expected.x, expected.y, expected.w),
+                "t = {t}"
+            );
+        }
+    }
+
+    #[test]
+    fn saturate_clamps_both_ends() {
+        assert_eq!(vec2(-0.5, 1.5).saturate(), vec2(0.0, 1.0));
+        assert_eq!(vec3(-3.0, 0.25, 7.0).saturate(), vec3(0.0, 0.25, 1.0));
+        assert_eq!(
+            vec4(2.0, -1.0, 0.0, 1.0).saturate(),
+            vec4(1.0, 0.0, 0.0, 1.0)
+        );
+        assert_eq!(
+            vec3(f32::NEG_INFINITY, 0.5, f32::INFINITY).saturate(),
+            vec3(0.0, 0.5, 1.0)
+        );
+    }
+
+    #[test]
+    fn negate_where_flips_selected_signs() {
+        assert_eq!(
+            vec4(1.0, -2.0, 3.0, -4.0).negate_where([true, false, false, true]),
+            vec4(-1.0, -2.0, 3.0, 4.0)
+        );
+        assert_eq!(
+            vec3(1.0, -2.0, 3.0).negate_where([false, true, false]),
+            vec3(1.0, 2.0, 3.0)
+        );
+        assert_eq!(vec2(1.0, -2.0).negate_where([true, true]), vec2(-1.0, 2.0));
+
+        // Zeros and NaNs change sign like any other value
+        let v = vec4(0.0, -0.0, f32::NAN, 5.0);
+        for bits in 0..16 {
+            let mask = [0, 1, 2, 3].map(|i| bits & (1 << i) != 0);
+            let res = v.negate_where(mask);
+            for (r, (c, m)) in [res.x, res.y, res.z, res.w]
+                .into_iter()
+                .zip([v.x, v.y, v.z, v.w].into_iter().zip(mask))
+            {
+                assert_eq!(r.to_bits(), negate_lane(c, m).to_bits(), "{mask:?}");
+            }
+        }
+    }
+}